@@ -0,0 +1,30 @@
+pub mod req;
+pub mod resp;
+
+pub use resp::Response;
+pub use req::{Request, RequestContext, Elb};
+
+use lambda_runtime::LambdaEvent;
+
+/// Event for lambda_runtime service function
+/// Used for building Lambda targets of Application Load Balancers
+///
+/// example:
+/// ```rust,no_run
+/// use lambda_runtime::{service_fn, Error};
+/// use hegel::{alb, http};
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Error> {
+///     let func = service_fn(func);
+///     lambda_runtime::run(func).await?;
+///     Ok(())
+/// }
+///
+/// async fn func(req: alb::Event) -> Result<alb::Response, Error> {
+///     let resp = http::Response::new_text(req.payload.path());
+///     Ok(alb::Response::from_http(resp, &req.payload))
+/// }
+/// ```
+///
+pub type Event = LambdaEvent<Request>;
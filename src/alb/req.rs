@@ -0,0 +1,192 @@
+use crate::common;
+use crate::http::{CookieJar, HeaderMap};
+use std::collections::HashMap;
+use serde::{Serialize, Deserialize};
+
+pub use common::ParseBodyError;
+
+/// **lambda_runtime** service payload type
+/// Used for building Lambda targets of Application Load Balancers
+///
+/// Depending on whether multi-value headers are enabled on the target group,
+/// either the single-value or the multi-value headers and queries are sent.
+/// Unlike API Gateway, the load balancer does not decode queries.
+///
+/// example:
+/// ```
+/// use hegel::{alb, http::StatusCode};
+/// use lambda_runtime::{Error, LambdaEvent};
+///
+/// async fn handler(req: LambdaEvent<alb::Request>) -> Result<alb::Response, Error> {
+///     //...
+///     Ok(alb::Response::new_status(StatusCode::OK).for_request(&req.payload))
+/// }
+/// ```
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    pub request_context: RequestContext,
+    pub http_method: String,
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query_string_parameters: Option<HashMap<String, String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multi_value_query_string_parameters: Option<HashMap<String, Vec<String>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headers: Option<HashMap<String, String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multi_value_headers: Option<HashMap<String, Vec<String>>>,
+    pub body: Option<String>,
+    #[serde(rename = "isBase64Encoded")]
+    pub is_base64encoded: bool,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestContext {
+    pub elb: Elb,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Elb {
+    pub target_group_arn: String,
+}
+
+impl Request {
+    /// Check whether multi-value headers are enabled on the target group
+    pub fn is_multi_value(&self) -> bool {
+        self.multi_value_headers.is_some()
+    }
+
+    /// Get user request body as String (UTF-8)
+    pub fn body(&self) -> Result<Option<String>, ParseBodyError> {
+        common::decode_body(&self.body, self.is_base64encoded)
+    }
+
+    /// Get user request body as binary
+    pub fn body_binary(&self) -> Result<Option<Vec<u8>>, base64::DecodeError> {
+        common::decode_body_binary(&self.body, self.is_base64encoded)
+    }
+
+    /// Get HTTP request path
+    ///
+    /// example: `/foo/bar`
+    pub fn path(&self) -> String {
+        self.path.clone()
+    }
+
+    /// Get user request cookies (parsed from the `Cookie` headers)
+    pub fn cookies(&self) -> CookieJar {
+        let headers = self.multi_value_headers();
+        let values = headers.iter()
+            .filter(|(k, _)| k.eq_ignore_ascii_case("cookie"))
+            .flat_map(|(_, v)| v.iter().map(|v| v.as_str()));
+        CookieJar::parse(values)
+    }
+
+    /// Get user request headers
+    ///
+    /// With multi-value headers enabled, headers sent multiple times keep all their values
+    ///
+    /// example:
+    /// ```
+    /// use std::collections::HashMap;
+    /// use hegel::alb;
+    ///
+    /// let mut req = alb::Request::default();
+    /// req.multi_value_headers = Some(HashMap::from([
+    ///     ("accept".to_string(), vec!["text/html".to_string(), "*/*".to_string()]),
+    /// ]));
+    /// let headers = req.headers();
+    /// assert_eq!(headers.get("Accept"), Some("text/html"));
+    /// assert_eq!(headers.get_all("accept"), vec!["text/html", "*/*"]);
+    /// ```
+    pub fn headers(&self) -> HeaderMap {
+        match &self.multi_value_headers {
+            Some(m) => m.iter()
+                .flat_map(|(k, v)| v.iter().map(move |v| (k.as_str(), v.as_str())))
+                .collect(),
+            None => self.headers.clone().unwrap_or_default().into(),
+        }
+    }
+
+    /// Get user request headers with all values of repeated headers
+    pub fn multi_value_headers(&self) -> HashMap<String, Vec<String>> {
+        match &self.headers {
+            Some(h) if self.multi_value_headers.is_none() => single_values(h),
+            _ => self.multi_value_headers.clone().unwrap_or_default(),
+        }
+    }
+
+    /// Get user request queries (still percent-encoded)
+    ///
+    /// With multi-value headers enabled, repeated keys are represented by their last value
+    pub fn queries(&self) -> Option<HashMap<String, String>> {
+        match &self.multi_value_query_string_parameters {
+            Some(m) => Some(last_values(m)),
+            None => self.query_string_parameters.clone(),
+        }
+    }
+
+    /// Get user request queries (still percent-encoded) with all values of repeated keys
+    pub fn multi_value_queries(&self) -> Option<HashMap<String, Vec<String>>> {
+        match &self.query_string_parameters {
+            Some(q) if self.multi_value_query_string_parameters.is_none() => Some(single_values(q)),
+            _ => self.multi_value_query_string_parameters.clone(),
+        }
+    }
+
+    /// Get user request method
+    ///
+    /// example: `GET`, `POST`, `DELETE` ...
+    pub fn method(&self) -> String {
+        self.http_method.clone()
+    }
+
+    /// Get user request IP (the first address of `X-Forwarded-For`)
+    pub fn ip(&self) -> String {
+        self.header("x-forwarded-for")
+            .and_then(|v| v.split(',').next().map(|v| v.trim().to_string()))
+            .unwrap_or_default()
+    }
+
+    /// Get user request User-Agent
+    pub fn ua(&self) -> String {
+        self.header("user-agent").unwrap_or_default()
+    }
+
+    /// Get the protocol the client used to connect to the load balancer
+    ///
+    /// example: `http`, `https`
+    pub fn protocol(&self) -> String {
+        self.header("x-forwarded-proto").unwrap_or_default()
+    }
+
+    /// Get the ARN of the target group the request was routed by
+    pub fn target_group_arn(&self) -> String {
+        self.request_context.elb.target_group_arn.clone()
+    }
+
+    fn header(&self, name: &str) -> Option<String> {
+        match &self.multi_value_headers {
+            Some(m) => m.iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case(name))
+                .and_then(|(_, v)| v.last().cloned()),
+            None => self.headers.as_ref()?
+                .iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case(name))
+                .map(|(_, v)| v.clone()),
+        }
+    }
+}
+
+fn last_values(m: &HashMap<String, Vec<String>>) -> HashMap<String, String> {
+    m.iter()
+        .filter_map(|(k, v)| v.last().map(|v| (k.clone(), v.clone())))
+        .collect()
+}
+
+fn single_values(m: &HashMap<String, String>) -> HashMap<String, Vec<String>> {
+    m.iter().map(|(k, v)| (k.clone(), vec![v.clone()])).collect()
+}
@@ -0,0 +1,145 @@
+use super::Request;
+use std::collections::HashMap;
+use serde::{Serialize, Deserialize};
+
+/// **lambda_runtime** service function return payload type
+/// Used for building Lambda targets of Application Load Balancers
+///
+/// Build it from an **http::Response** with `from_http`,
+/// so the headers are sent in the form the target group expects.
+/// Only the last cookie is kept when multi-value headers are disabled.
+///
+/// example:
+/// ```
+/// use hegel::{alb, http};
+/// use lambda_runtime::Error;
+///
+/// async fn handler(req: alb::Event) -> Result<alb::Response, Error> {
+///     let resp = http::Response::new_json("{}".to_string());
+///     Ok(alb::Response::from_http(resp, &req.payload))
+/// }
+/// ```
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Response {
+    #[serde(rename = "isBase64Encoded")]
+    pub is_base64encoded: bool,
+    pub status_code: u16,
+    pub status_description: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headers: Option<HashMap<String, String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multi_value_headers: Option<HashMap<String, Vec<String>>>,
+    pub body: String,
+}
+
+impl Response {
+    /// return a HTTP status as Response
+    pub fn new_status(s: crate::http::StatusCode) -> Response {
+        crate::http::Response::new_status(s).into()
+    }
+
+    /// return a HTTP status number as Response,
+    /// or an error when it is not a valid status code (`100..=599`)
+    pub fn try_new_status(s: u16) -> Result<Response, crate::http::InvalidStatusCode> {
+        crate::http::Response::try_new_status(s).map(Into::into)
+    }
+
+    /// return a Response with provided header added
+    /// like s struct builder
+    pub fn header(mut self, k: String, v: String) -> Response {
+        match &mut self.multi_value_headers {
+            Some(m) => m.entry(k).or_default().push(v),
+            None => {
+                self.headers.get_or_insert_with(HashMap::new).insert(k, v);
+            }
+        }
+        self
+    }
+
+    /// return the http::Response as Response with headers in the form matching the request:
+    /// multi-value headers (every cookie kept) when they are enabled on the target group,
+    /// single-value headers otherwise
+    ///
+    /// example:
+    /// ```
+    /// use hegel::{alb, http};
+    ///
+    /// let resp = http::Response::new_text("hi".to_string())
+    ///     .cookie(http::SetCookie::new("a".to_string(), "1".to_string()))
+    ///     .cookie(http::SetCookie::new("b".to_string(), "2".to_string()));
+    ///
+    /// let mut req = alb::Request::default();
+    /// let single = alb::Response::from_http(resp.clone(), &req);
+    /// assert_eq!(single.headers.as_ref().unwrap()["Set-Cookie"], "b=2");
+    /// assert!(single.multi_value_headers.is_none());
+    ///
+    /// req.multi_value_headers = Some(Default::default());
+    /// let multi = alb::Response::from_http(resp, &req);
+    /// let headers = multi.multi_value_headers.as_ref().unwrap();
+    /// assert_eq!(headers["Set-Cookie"], vec!["a=1", "b=2"]);
+    /// assert_eq!(headers["Content-Type"], vec!["text/plain; charset=utf-8"]);
+    /// assert!(multi.headers.is_none());
+    /// ```
+    pub fn from_http(r: crate::http::Response, req: &Request) -> Response {
+        if !req.is_multi_value() {
+            return r.into();
+        }
+        let mut headers: HashMap<String, Vec<String>> = HashMap::new();
+        for (k, v) in r.headers.iter() {
+            headers.entry(k.to_string()).or_default().push(v.to_string());
+        }
+        if !r.cookies.is_empty() {
+            headers.insert("Set-Cookie".to_string(), r.cookies);
+        }
+        Response {
+            is_base64encoded: r.is_base64encoded,
+            status_code: r.status_code.into(),
+            status_description: r.status_code.to_string(),
+            headers: None,
+            multi_value_headers: Some(headers),
+            body: r.body,
+        }
+    }
+
+    /// return a Response with headers in the form matching the request:
+    /// multi-value headers when they are enabled on the target group, single-value headers otherwise
+    pub fn for_request(mut self, req: &Request) -> Response {
+        if req.is_multi_value() {
+            if let Some(headers) = self.headers.take() {
+                let m = self.multi_value_headers.get_or_insert_with(HashMap::new);
+                for (k, v) in headers {
+                    m.entry(k).or_default().push(v);
+                }
+            }
+            self.multi_value_headers.get_or_insert_with(HashMap::new);
+        } else if let Some(m) = self.multi_value_headers.take() {
+            let headers = self.headers.get_or_insert_with(HashMap::new);
+            for (k, v) in m {
+                if let Some(v) = v.last() {
+                    headers.insert(k, v.clone());
+                }
+            }
+        }
+        self
+    }
+}
+
+/// Single-value headers, the default of target groups,
+/// use `Response::from_http` to match the request instead
+impl From<crate::http::Response> for Response {
+    fn from(r: crate::http::Response) -> Self {
+        let mut headers: HashMap<String, String> = r.headers.into();
+        if let Some(cookie) = r.cookies.last() {
+            headers.insert("Set-Cookie".to_string(), cookie.clone());
+        }
+        Response {
+            is_base64encoded: r.is_base64encoded,
+            status_code: r.status_code.into(),
+            status_description: r.status_code.to_string(),
+            headers: Some(headers),
+            multi_value_headers: None,
+            body: r.body,
+        }
+    }
+}
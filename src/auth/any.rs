@@ -0,0 +1,171 @@
+use super::{v1, Request, RouteArn};
+use super::policy::ParseArnError;
+use std::collections::HashMap;
+use serde::{Serialize, Deserialize, Deserializer};
+use serde::de::Error;
+
+/// **lambda_runtime** service payload type accepting every authorizer event
+/// Used for building API Gateway Lambda Authorizers for both HTTP APIs and REST APIs
+///
+/// Format 2.0 payloads are picked by their `version` field, the others by their `type` field.
+/// Only format 2.0 authorizers may answer with the simple **auth::Response**,
+/// so an **auth::PolicyResponse** is the response that suits every variant.
+///
+/// example:
+/// ```
+/// use hegel::auth;
+/// use lambda_runtime::{Error, LambdaEvent};
+///
+/// async fn handler(req: LambdaEvent<auth::AnyRequest>) -> Result<auth::PolicyResponse, Error> {
+///     let response = auth::PolicyResponse::new("user".to_string());
+///     match req.payload.token() {
+///         Some(token) if token == "secret" => Ok(response.allow(req.payload.arn()?)),
+///         _ => Ok(response.deny(req.payload.arn()?)),
+///     }
+/// }
+/// ```
+///
+/// REST API `REQUEST` authorizer event (AWS sample):
+/// ```
+/// use hegel::auth;
+///
+/// let event = r#"{
+///   "type": "REQUEST",
+///   "methodArn": "arn:aws:execute-api:us-east-1:123456789012:abcdef123/test/GET/request",
+///   "resource": "/request",
+///   "path": "/request",
+///   "httpMethod": "GET",
+///   "headers": {
+///     "X-AMZ-Date": "20170718T062915Z",
+///     "Accept": "*/*",
+///     "HeaderAuth1": "headerValue1",
+///     "CloudFront-Viewer-Country": "US",
+///     "CloudFront-Forwarded-Proto": "https",
+///     "CloudFront-Is-Tablet-Viewer": "false",
+///     "CloudFront-Is-Mobile-Viewer": "false",
+///     "User-Agent": "..."
+///   },
+///   "queryStringParameters": {
+///     "QueryString1": "queryValue1"
+///   },
+///   "pathParameters": {},
+///   "stageVariables": {
+///     "StageVar1": "stageValue1"
+///   },
+///   "requestContext": {
+///     "path": "/request",
+///     "accountId": "123456789012",
+///     "resourceId": "05c7jb",
+///     "stage": "test",
+///     "requestId": "...",
+///     "identity": {
+///       "apiKey": "...",
+///       "sourceIp": "...",
+///       "clientCert": {
+///         "clientCertPem": "CERT_CONTENT",
+///         "subjectDN": "www.example.com",
+///         "issuerDN": "Example issuer",
+///         "serialNumber": "a1:a1:a1:a1:a1:a1:a1:a1:a1:a1:a1:a1:a1:a1:a1:a1",
+///         "validity": {
+///           "notBefore": "May 28 12:30:02 2019 GMT",
+///           "notAfter": "Aug  5 09:36:04 2021 GMT"
+///         }
+///       }
+///     },
+///     "resourcePath": "/request",
+///     "httpMethod": "GET",
+///     "apiId": "abcdef123"
+///   }
+/// }"#;
+///
+/// let req: auth::AnyRequest = serde_json::from_str(event).unwrap();
+/// assert!(matches!(req, auth::AnyRequest::V1(_)));
+/// assert_eq!(req.path(), Some("/request".to_string()));
+/// assert_eq!(req.headers().get("HeaderAuth1"), Some(&"headerValue1".to_string()));
+/// assert_eq!(req.arn().unwrap().stage, "test");
+///
+/// let req: auth::v1::Request = serde_json::from_str(event).unwrap();
+/// assert_eq!(req.request_context.request_time_epoch, 0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum AnyRequest {
+    V2(Box<Request>),
+    V1(Box<v1::Request>),
+    Token(v1::TokenRequest),
+}
+
+impl<'de> Deserialize<'de> for AnyRequest {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        if value.get("version").and_then(|v| v.as_str()) == Some("2.0") {
+            return Request::deserialize(value).map(|r| AnyRequest::V2(Box::new(r))).map_err(D::Error::custom);
+        }
+        match value.get("type").and_then(|v| v.as_str()) {
+            Some("TOKEN") => v1::TokenRequest::deserialize(value).map(AnyRequest::Token).map_err(D::Error::custom),
+            Some("REQUEST") => v1::Request::deserialize(value).map(|r| AnyRequest::V1(Box::new(r))).map_err(D::Error::custom),
+            Some(t) => Err(D::Error::custom(format!("unsupported authorizer type: {}", t))),
+            None => Err(D::Error::missing_field("type")),
+        }
+    }
+}
+
+impl AnyRequest {
+    /// Get the route ARN (format 2.0) or method ARN (others) parsed as **RouteArn**
+    pub fn arn(&self) -> Result<RouteArn, ParseArnError> {
+        match self {
+            AnyRequest::V2(r) => r.arn(),
+            AnyRequest::V1(r) => r.arn(),
+            AnyRequest::Token(r) => r.arn(),
+        }
+    }
+
+    /// Get the token the caller presented
+    ///
+    /// For `TOKEN` authorizers it is the configured token header,
+    /// otherwise the first identity source
+    pub fn token(&self) -> Option<String> {
+        match self {
+            AnyRequest::V2(r) => r.identity_source.first().cloned(),
+            AnyRequest::V1(r) => r.authorization_token.clone()
+                .or_else(|| r.identity_source.clone()),
+            AnyRequest::Token(r) => Some(r.authorization_token.clone()),
+        }
+    }
+
+    /// Get user request headers
+    ///
+    /// `TOKEN` authorizers receive no headers
+    pub fn headers(&self) -> HashMap<String, String> {
+        match self {
+            AnyRequest::V2(r) => r.headers(),
+            AnyRequest::V1(r) => r.headers(),
+            AnyRequest::Token(_) => HashMap::new(),
+        }
+    }
+
+    /// Get HTTP request path
+    ///
+    /// `TOKEN` authorizers receive no path
+    pub fn path(&self) -> Option<String> {
+        match self {
+            AnyRequest::V2(r) => Some(r.path()),
+            AnyRequest::V1(r) => Some(r.path()),
+            AnyRequest::Token(_) => None,
+        }
+    }
+
+    /// Get user request method
+    ///
+    /// `TOKEN` authorizers receive no method
+    pub fn method(&self) -> Option<String> {
+        match self {
+            AnyRequest::V2(r) => Some(r.method()),
+            AnyRequest::V1(r) => Some(r.method()),
+            AnyRequest::Token(_) => None,
+        }
+    }
+}
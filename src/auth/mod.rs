@@ -1,8 +1,14 @@
 pub mod req;
 pub mod resp;
+pub mod policy;
+pub mod v1;
+mod any;
 
 pub use resp::Response;
 pub use req::{Request, RequestSimple};
+pub use v1::TokenRequest;
+pub use any::AnyRequest;
+pub use policy::{PolicyResponse, PolicyDocument, Statement, Effect, RouteArn};
 
 use lambda_runtime::LambdaEvent;
 
@@ -10,7 +16,7 @@ use lambda_runtime::LambdaEvent;
 /// Used for building API Gateway Lambda Authorizers for HTTP APIs
 ///
 /// example:
-/// ```rust
+/// ```rust,no_run
 /// use lambda_runtime::{service_fn, Error};
 /// use hegel::auth;
 ///
@@ -23,8 +29,12 @@ use lambda_runtime::LambdaEvent;
 ///
 /// async fn func(req: auth::Event) -> Result<auth::Response, Error> {
 ///     // ...
-///     Ok(auth::Response::new_status(200))
+///     Ok(auth::Response::new_nc(true))
 /// }
 /// ```
 ///
-pub type Event = LambdaEvent<Request>;
\ No newline at end of file
+pub type Event = LambdaEvent<Request>;
+
+/// Event for **lambda_runtime** service function accepting every authorizer event
+/// Used for building API Gateway Lambda Authorizers for both HTTP APIs and REST APIs
+pub type AnyEvent = LambdaEvent<AnyRequest>;
@@ -0,0 +1,254 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use serde::{Serialize, Deserialize};
+
+/// IAM policy version used by API Gateway authorizers
+pub const POLICY_VERSION: &str = "2012-10-17";
+
+/// Action allowing a caller to invoke an API Gateway route
+pub const INVOKE_ACTION: &str = "execute-api:Invoke";
+
+/// **lambda_runtime** service function return payload type in IAM policy form
+/// Used for building API Gateway Lambda Authorizers for HTTP APIs
+///
+/// example:
+/// ```
+/// use hegel::auth;
+/// use lambda_runtime::Error;
+///
+/// async fn handler(req: auth::Event) -> Result<auth::PolicyResponse, Error> {
+///     let arn = req.payload.arn()?;
+///     Ok(auth::PolicyResponse::new("user".to_string())
+///         // any method on the same path
+///         .allow(arn.clone().any_method())
+///         // but nothing below /admin
+///         .deny(arn.with_path("admin/*".to_string())))
+/// }
+/// ```
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PolicyResponse {
+    pub principal_id: String,
+    pub policy_document: PolicyDocument,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub context: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage_identifier_key: Option<String>,
+}
+
+/// IAM policy document
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct PolicyDocument {
+    pub version: String,
+    pub statement: Vec<Statement>,
+}
+
+/// IAM policy statement
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Statement {
+    pub action: Vec<String>,
+    pub effect: Effect,
+    pub resource: Vec<String>,
+}
+
+/// Effect of an IAM policy statement
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Effect {
+    Allow,
+    Deny,
+}
+
+impl Default for PolicyDocument {
+    fn default() -> Self {
+        PolicyDocument {
+            version: POLICY_VERSION.to_string(),
+            statement: Vec::new(),
+        }
+    }
+}
+
+impl PolicyResponse {
+    /// new **PolicyResponse** for a principal, denying everything until
+    /// resources are allowed
+    pub fn new(principal_id: String) -> PolicyResponse {
+        PolicyResponse {
+            principal_id,
+            ..Default::default()
+        }
+    }
+
+    /// return a PolicyResponse allowing invocation of the provided resource
+    /// like s struct builder
+    ///
+    /// The resource is usually a **RouteArn**, but any ARN string is accepted
+    pub fn allow<R: ToString>(self, resource: R) -> PolicyResponse {
+        self.statement(Effect::Allow, resource.to_string())
+    }
+
+    /// return a PolicyResponse denying invocation of the provided resource
+    /// like s struct builder
+    ///
+    /// An explicit deny always wins over an allow for the same request
+    pub fn deny<R: ToString>(self, resource: R) -> PolicyResponse {
+        self.statement(Effect::Deny, resource.to_string())
+    }
+
+    /// return a PolicyResponse with provided context added
+    /// like s struct builder
+    pub fn context(mut self, k: String, v: String) -> PolicyResponse {
+        self.context.insert(k, v);
+        self
+    }
+
+    /// return a PolicyResponse with provided usage identifier key (API key) added
+    /// like s struct builder
+    pub fn usage_identifier_key(mut self, key: String) -> PolicyResponse {
+        self.usage_identifier_key = Some(key);
+        self
+    }
+
+    fn statement(mut self, effect: Effect, resource: String) -> PolicyResponse {
+        let statements = &mut self.policy_document.statement;
+        match statements.iter_mut().find(|s| s.effect == effect) {
+            Some(s) => s.resource.push(resource),
+            None => statements.push(Statement {
+                action: vec![INVOKE_ACTION.to_string()],
+                effect,
+                resource: vec![resource],
+            }),
+        }
+        self
+    }
+}
+
+/// Parsed API Gateway route ARN
+///
+/// format: `arn:{partition}:execute-api:{region}:{account_id}:{api_id}/{stage}/{method}/{path}`
+///
+/// example:
+/// ```
+/// use hegel::auth::RouteArn;
+///
+/// let arn: RouteArn = "arn:aws:execute-api:us-east-1:123456789012:abcdef123/$default/GET/items/42"
+///     .parse()
+///     .unwrap();
+/// assert_eq!(arn.method, "GET");
+/// assert_eq!(arn.path, "items/42");
+/// assert_eq!(
+///     arn.any_method().any_segment(1).to_string(),
+///     "arn:aws:execute-api:us-east-1:123456789012:abcdef123/$default/*/items/*"
+/// );
+/// ```
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct RouteArn {
+    pub partition: String,
+    pub region: String,
+    pub account_id: String,
+    pub api_id: String,
+    pub stage: String,
+    pub method: String,
+    /// path without the leading `/`
+    pub path: String,
+}
+
+/// Error returned when a string is not a valid API Gateway route ARN
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseArnError(pub String);
+
+impl fmt::Display for ParseArnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid API Gateway route ARN: {}", self.0)
+    }
+}
+
+impl std::error::Error for ParseArnError {}
+
+impl FromStr for RouteArn {
+    type Err = ParseArnError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ParseArnError(s.to_string());
+        let parts = s.splitn(6, ':').collect::<Vec<&str>>();
+        if parts.len() != 6 || parts[0] != "arn" || parts[2] != "execute-api" {
+            return Err(err());
+        }
+        let mut route = parts[5].splitn(4, '/');
+        let api_id = route.next().ok_or_else(err)?;
+        let stage = route.next().ok_or_else(err)?;
+        let method = route.next().ok_or_else(err)?;
+        let path = route.next().unwrap_or_default();
+        Ok(RouteArn {
+            partition: parts[1].to_string(),
+            region: parts[3].to_string(),
+            account_id: parts[4].to_string(),
+            api_id: api_id.to_string(),
+            stage: stage.to_string(),
+            method: method.to_string(),
+            path: path.to_string(),
+        })
+    }
+}
+
+impl fmt::Display for RouteArn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "arn:{}:execute-api:{}:{}:{}/{}/{}/{}",
+            self.partition, self.region, self.account_id, self.api_id, self.stage, self.method, self.path
+        )
+    }
+}
+
+impl RouteArn {
+    /// return a RouteArn with provided stage
+    pub fn with_stage(mut self, stage: String) -> RouteArn {
+        self.stage = stage;
+        self
+    }
+
+    /// return a RouteArn matching any stage
+    pub fn any_stage(self) -> RouteArn {
+        self.with_stage("*".to_string())
+    }
+
+    /// return a RouteArn with provided method
+    ///
+    /// example: `GET`, `POST`, `*`
+    pub fn with_method(mut self, method: String) -> RouteArn {
+        self.method = method;
+        self
+    }
+
+    /// return a RouteArn matching any method
+    pub fn any_method(self) -> RouteArn {
+        self.with_method("*".to_string())
+    }
+
+    /// return a RouteArn with provided path (a leading `/` is ignored)
+    ///
+    /// example: `items/42`, `items/*`
+    pub fn with_path(mut self, path: String) -> RouteArn {
+        self.path = path.trim_start_matches('/').to_string();
+        self
+    }
+
+    /// return a RouteArn matching any path
+    pub fn any_path(self) -> RouteArn {
+        self.with_path("*".to_string())
+    }
+
+    /// return a RouteArn whose path segment at provided index (starting at 0) matches anything
+    ///
+    /// Indexes past the end of the path are ignored
+    pub fn any_segment(mut self, index: usize) -> RouteArn {
+        let mut segments = self.path.split('/').collect::<Vec<&str>>();
+        if let Some(segment) = segments.get_mut(index) {
+            *segment = "*";
+        }
+        self.path = segments.join("/");
+        self
+    }
+}
@@ -2,6 +2,8 @@ use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use std::collections::HashMap;
 use serde::{Serialize, Deserialize};
 use crate::common;
+use crate::http::CookieJar;
+use super::policy::{RouteArn, ParseArnError};
 
 #[cfg(feature = "chrono")]
 use chrono::{Utc, TimeZone, DateTime};
@@ -73,21 +75,8 @@ impl RequestSimple {
     }
 
     /// Get user request cookies
-    pub fn cookies(&self) -> Option<HashMap<String, String>> {
-        let cs = self.cookies.clone();
-        if cs.is_none() {
-            return None
-        }
-        let cs = cs.unwrap();
-        let mut result = HashMap::new();
-        for c in cs {
-            let spl = c.split("=").collect::<Vec<&str>>();
-            if spl.len() != 2 {
-                continue;
-            }
-            result.insert(spl[0].to_string(), spl[1].to_string());
-        }
-        Some(result)
+    pub fn cookies(&self) -> CookieJar {
+        CookieJar::parse(self.cookies.iter().flatten().map(|c| c.as_str()))
     }
 
     /// Get user request headers
@@ -168,6 +157,11 @@ impl RequestSimple {
 
 
 impl Request {
+    /// Get the route ARN of the request parsed as **RouteArn**
+    pub fn arn(&self) -> Result<RouteArn, ParseArnError> {
+        self.route_arn.parse()
+    }
+
     /// Get HTTP request path
     ///
     /// example: `/foo/bar`
@@ -176,21 +170,8 @@ impl Request {
     }
 
     /// Get user request cookies
-    pub fn cookies(&self) -> Option<HashMap<String, String>> {
-        let cs = self.cookies.clone();
-        if cs.is_none() {
-            return None
-        }
-        let cs = cs.unwrap();
-        let mut result = HashMap::new();
-        for c in cs {
-            let spl = c.split("=").collect::<Vec<&str>>();
-            if spl.len() != 2 {
-                continue;
-            }
-            result.insert(spl[0].to_string(), spl[1].to_string());
-        }
-        Some(result)
+    pub fn cookies(&self) -> CookieJar {
+        CookieJar::parse(self.cookies.iter().flatten().map(|c| c.as_str()))
     }
 
     /// Get user request headers
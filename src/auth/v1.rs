@@ -0,0 +1,160 @@
+use crate::common;
+use crate::http::CookieJar;
+use super::policy::{RouteArn, ParseArnError};
+use std::collections::HashMap;
+use serde::{Serialize, Deserialize};
+
+/// **lambda_runtime** service payload type of `TOKEN` authorizers
+/// Used for building API Gateway Lambda Authorizers for REST APIs
+///
+/// `TOKEN` authorizers must answer with an **auth::PolicyResponse**
+///
+/// example:
+/// ```
+/// use hegel::auth;
+/// use lambda_runtime::{Error, LambdaEvent};
+///
+/// async fn handler(req: LambdaEvent<auth::TokenRequest>) -> Result<auth::PolicyResponse, Error> {
+///     let arn = req.payload.arn()?;
+///     if req.payload.authorization_token == "allow" {
+///         return Ok(auth::PolicyResponse::new("user".to_string()).allow(arn));
+///     }
+///     Ok(auth::PolicyResponse::new("user".to_string()).deny(arn))
+/// }
+/// ```
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenRequest {
+    #[serde(rename = "type")]
+    pub type_field: String,
+    pub authorization_token: String,
+    pub method_arn: String,
+}
+
+/// **lambda_runtime** service payload type of `REQUEST` authorizers (format 1.0)
+/// Used for building API Gateway Lambda Authorizers for REST APIs,
+/// and for HTTP APIs configured with payload format 1.0
+///
+/// These authorizers must answer with an **auth::PolicyResponse**
+///
+/// example:
+/// ```
+/// use hegel::auth;
+/// use lambda_runtime::{Error, LambdaEvent};
+///
+/// async fn handler(req: LambdaEvent<auth::v1::Request>) -> Result<auth::PolicyResponse, Error> {
+///     //...
+///     Ok(auth::PolicyResponse::new("user".to_string()).allow(req.payload.arn()?))
+/// }
+/// ```
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    #[serde(default)]
+    pub version: String,
+    #[serde(rename = "type")]
+    pub type_field: String,
+    pub method_arn: String,
+    pub identity_source: Option<String>,
+    pub authorization_token: Option<String>,
+    pub resource: String,
+    pub path: String,
+    pub http_method: String,
+    pub headers: Option<HashMap<String, String>>,
+    pub multi_value_headers: Option<HashMap<String, Vec<String>>>,
+    pub query_string_parameters: Option<HashMap<String, String>>,
+    pub multi_value_query_string_parameters: Option<HashMap<String, Vec<String>>>,
+    pub path_parameters: Option<HashMap<String, String>>,
+    pub stage_variables: Option<HashMap<String, String>>,
+    pub request_context: common::RequestContextV1,
+}
+
+impl TokenRequest {
+    /// Get the method ARN of the request parsed as **RouteArn**
+    pub fn arn(&self) -> Result<RouteArn, ParseArnError> {
+        self.method_arn.parse()
+    }
+}
+
+impl Request {
+    /// Get the method ARN of the request parsed as **RouteArn**
+    pub fn arn(&self) -> Result<RouteArn, ParseArnError> {
+        self.method_arn.parse()
+    }
+
+    /// Get HTTP request path
+    ///
+    /// example: `/foo/bar`
+    pub fn path(&self) -> String {
+        self.path.clone()
+    }
+
+    /// Get user request cookies (parsed from the `Cookie` headers)
+    ///
+    /// Read from `multiValueHeaders`, or from `headers` when the event has none
+    ///
+    /// example:
+    /// ```
+    /// use std::collections::HashMap;
+    /// use hegel::auth;
+    ///
+    /// let mut req = auth::v1::Request::default();
+    /// req.headers = Some(HashMap::from([("Cookie".to_string(), "a=1; b=2".to_string())]));
+    /// assert_eq!(req.cookies().get("b"), Some("2"));
+    /// ```
+    pub fn cookies(&self) -> CookieJar {
+        match &self.multi_value_headers {
+            Some(m) => CookieJar::parse(m.iter()
+                .filter(|(k, _)| k.eq_ignore_ascii_case("cookie"))
+                .flat_map(|(_, v)| v.iter().map(|v| v.as_str()))),
+            None => CookieJar::parse(self.headers.iter()
+                .flatten()
+                .filter(|(k, _)| k.eq_ignore_ascii_case("cookie"))
+                .map(|(_, v)| v.as_str())),
+        }
+    }
+
+    /// Get user request headers
+    pub fn headers(&self) -> HashMap<String, String> {
+        self.headers.clone().unwrap_or_default()
+    }
+
+    /// Get user request headers with all values of repeated headers
+    pub fn multi_value_headers(&self) -> HashMap<String, Vec<String>> {
+        self.multi_value_headers.clone().unwrap_or_default()
+    }
+
+    /// Get user request queries
+    pub fn queries(&self) -> Option<HashMap<String, String>> {
+        self.query_string_parameters.clone()
+    }
+
+    /// Get route params
+    pub fn params(&self) -> Option<HashMap<String, String>> {
+        self.path_parameters.clone()
+    }
+
+    /// Get API Gateway stage
+    ///
+    /// example: `prod`
+    pub fn stage(&self) -> String {
+        self.request_context.stage.clone()
+    }
+
+    /// Get user request method
+    ///
+    /// example: `GET`, `POST`, `DELETE` ...
+    pub fn method(&self) -> String {
+        self.http_method.clone()
+    }
+
+    /// Get user request IP
+    pub fn ip(&self) -> String {
+        self.request_context.identity.source_ip.clone()
+    }
+
+    /// Get user request User-Agent
+    pub fn ua(&self) -> String {
+        self.request_context.identity.user_agent.clone().unwrap_or_default()
+    }
+}
@@ -2,9 +2,6 @@ use std::collections::HashMap;
 #[cfg(feature = "binary")]
 use lambda_runtime::{service_fn, Error};
 
-#[cfg(feature = "binary")]
-use serde_json;
-
 #[cfg(feature = "binary")]
 use hegel::auth;
 
@@ -20,7 +17,7 @@ async fn main() -> Result<(), Error> {
 async fn func(req: auth::Event) -> Result<auth::Response, Error> {
     // print to log
     println!("{}", serde_json::to_string(&req.payload).unwrap());
-    return match req.payload.path().as_str() {
+    match req.payload.path().as_str() {
         "/" => Ok(auth::Response::new_nc(true)),
         "/pass" => Ok(auth::Response::new_nc(true)),
         "/pass_with_context" => {
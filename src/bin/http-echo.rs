@@ -1,8 +1,6 @@
 #[cfg(feature = "binary")]
 use lambda_runtime::{service_fn, Error};
 #[cfg(feature = "binary")]
-use serde_json;
-#[cfg(feature = "binary")]
 use hegel::http;
 
 #[cfg(feature = "binary")]
@@ -18,6 +16,6 @@ async fn func(req: http::Event) -> Result<http::Response, Error> {
     // print to log
     println!("{}", serde_json::to_string(&req.payload).unwrap());
     let js = serde_json::to_string(&req.payload);
-    if js.is_err() {return Ok(http::Response::new_status(500).body_text("Can not encode as json".to_string()))}
-    return Ok(http::Response::new_json(js.unwrap()))
+    if js.is_err() {return Ok(http::Response::new_status(http::StatusCode::INTERNAL_SERVER_ERROR).body_text("Can not encode as json".to_string()))}
+    Ok(http::Response::new_json(js.unwrap()))
 }
\ No newline at end of file
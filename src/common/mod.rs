@@ -1,6 +1,26 @@
 use std::collections::HashMap;
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use serde::{Serialize, Deserialize};
 
+/// Enum type of errors that may occur during request body parsing
+#[derive(Debug, Clone)]
+pub enum ParseBodyError {
+    Base64DecodeError(base64::DecodeError),
+    FromUtf8Error(std::string::FromUtf8Error)
+}
+
+impl fmt::Display for ParseBodyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseBodyError::Base64DecodeError(e) => write!(f, "invalid base64 body: {}", e),
+            ParseBodyError::FromUtf8Error(e) => write!(f, "body is not valid UTF-8: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ParseBodyError {}
+
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RequestContextSimple {
@@ -52,6 +72,29 @@ pub struct ClientCert {
 pub struct Authorizer {
     pub lambda: Option<HashMap<String, String>>,
     pub jwt: Option<HashMap<String,String>>,
+    pub iam: Option<IamAuthorizer>,
+}
+
+/// Caller identity of requests authorized by AWS IAM
+/// (HTTP APIs with IAM authorization and Lambda function URLs with `AWS_IAM` auth type)
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IamAuthorizer {
+    pub access_key: Option<String>,
+    pub account_id: Option<String>,
+    pub caller_id: Option<String>,
+    pub cognito_identity: Option<CognitoIdentity>,
+    pub principal_org_id: Option<String>,
+    pub user_arn: Option<String>,
+    pub user_id: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CognitoIdentity {
+    pub amr: Vec<String>,
+    pub identity_id: String,
+    pub identity_pool_id: String,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -69,4 +112,119 @@ pub struct Http {
     pub protocol: String,
     pub source_ip: String,
     pub user_agent: String,
-}
\ No newline at end of file
+}
+
+/// Request context of payload format 1.0
+///
+/// REST API authorizer and test invoke events leave out some of the fields,
+/// missing ones are left empty.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestContextV1 {
+    #[serde(default)]
+    pub account_id: String,
+    #[serde(default)]
+    pub api_id: String,
+    pub authorizer: Option<HashMap<String, serde_json::Value>>,
+    pub domain_name: Option<String>,
+    pub domain_prefix: Option<String>,
+    pub extended_request_id: Option<String>,
+    #[serde(default)]
+    pub http_method: String,
+    #[serde(default)]
+    pub identity: Identity,
+    pub path: Option<String>,
+    pub protocol: Option<String>,
+    #[serde(default)]
+    pub request_id: String,
+    pub request_time: Option<String>,
+    #[serde(default)]
+    pub request_time_epoch: u64,
+    pub resource_id: Option<String>,
+    pub resource_path: Option<String>,
+    #[serde(default)]
+    pub stage: String,
+}
+
+/// Caller identity of payload format 1.0 and REST APIs
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Identity {
+    pub access_key: Option<String>,
+    pub account_id: Option<String>,
+    pub api_key: Option<String>,
+    pub api_key_id: Option<String>,
+    pub caller: Option<String>,
+    pub client_cert: Option<ClientCert>,
+    pub cognito_amr: Option<Vec<String>>,
+    pub cognito_authentication_provider: Option<String>,
+    pub cognito_authentication_type: Option<String>,
+    pub cognito_identity_id: Option<String>,
+    pub cognito_identity_pool_id: Option<String>,
+    pub principal_org_id: Option<String>,
+    #[serde(default)]
+    pub source_ip: String,
+    pub user: Option<String>,
+    pub user_agent: Option<String>,
+    pub user_arn: Option<String>,
+}
+
+/// Decode a (possibly base64 encoded) request body as String (UTF-8)
+pub(crate) fn decode_body(body: &Option<String>, is_base64encoded: bool) -> Result<Option<String>, ParseBodyError> {
+    let body = match body {
+        Some(body) => body,
+        None => return Ok(None),
+    };
+    if !is_base64encoded {
+        return Ok(Some(body.clone()));
+    }
+    let bytes = base64::decode(body).map_err(ParseBodyError::Base64DecodeError)?;
+    String::from_utf8(bytes).map(Some).map_err(ParseBodyError::FromUtf8Error)
+}
+
+/// Decode a (possibly base64 encoded) request body as binary
+pub(crate) fn decode_body_binary(body: &Option<String>, is_base64encoded: bool) -> Result<Option<Vec<u8>>, base64::DecodeError> {
+    match body {
+        None => Ok(None),
+        Some(body) if !is_base64encoded => Ok(Some(body.as_bytes().to_vec())),
+        Some(body) => base64::decode(body).map(Some),
+    }
+}
+
+/// Decode `%XX` escapes, returning the input unchanged when the result is not valid UTF-8
+pub(crate) fn percent_decode(s: &str) -> String {
+    if !s.contains('%') {
+        return s.to_string();
+    }
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            if let (Some(h), Some(l)) = (hex_value(bytes[i + 1]), hex_value(bytes[i + 2])) {
+                out.push(h << 4 | l);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8(out).unwrap_or_else(|_| s.to_string())
+}
+
+fn hex_value(b: u8) -> Option<u8> {
+    (b as char).to_digit(16).map(|d| d as u8)
+}
+
+/// Format a time as an HTTP date, clamped to the years 1970 to 9999 an HTTP date can hold
+pub(crate) fn fmt_http_date(t: SystemTime) -> String {
+    // 9999-12-31T23:59:59Z
+    const MAX: Duration = Duration::from_secs(253_402_300_799);
+    let t = match t.duration_since(UNIX_EPOCH) {
+        Ok(d) if d > MAX => UNIX_EPOCH + MAX,
+        Ok(_) => t,
+        Err(_) => UNIX_EPOCH,
+    };
+    httpdate::fmt_http_date(t)
+}
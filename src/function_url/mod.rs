@@ -0,0 +1,37 @@
+//! Payloads of Lambda function URLs
+//!
+//! Function URLs send the HTTP API payload format 2.0 (with `$default` as route key and stage),
+//! so the types are shared with **hegel::http**.
+//! The IAM caller identity of `AWS_IAM` function URLs is available through
+//! `Request::iam`, `Request::user_arn`, `Request::access_key` and `Request::caller_account_id`.
+//!
+//! Only the `BUFFERED` invoke mode is supported, response streaming needs a newer **lambda_runtime**.
+
+pub use crate::http::{Request, Response};
+
+use lambda_runtime::LambdaEvent;
+
+/// Event for lambda_runtime service function
+/// Used for building Lambda function URLs
+///
+/// example:
+/// ```rust,no_run
+/// use lambda_runtime::{service_fn, Error};
+/// use hegel::{function_url, http::StatusCode};
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Error> {
+///     let func = service_fn(func);
+///     lambda_runtime::run(func).await?;
+///     Ok(())
+/// }
+///
+/// async fn func(req: function_url::Event) -> Result<function_url::Response, Error> {
+///     match req.payload.user_arn() {
+///         Some(arn) => Ok(function_url::Response::new_text(arn)),
+///         None => Ok(function_url::Response::new_status(StatusCode::FORBIDDEN)),
+///     }
+/// }
+/// ```
+///
+pub type Event = LambdaEvent<Request>;
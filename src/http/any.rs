@@ -0,0 +1,207 @@
+use super::{v1, Request, CookieJar, HeaderMap};
+use crate::common::ParseBodyError;
+use std::collections::HashMap;
+use std::time::SystemTime;
+use serde::{Serialize, Deserialize, Deserializer};
+use serde::de::Error;
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, Utc};
+
+/// **lambda_runtime** service payload type accepting both payload formats
+/// Used for building API Gateway Lambda proxy integrations for HTTP APIs
+///
+/// The format is picked by the `version` field of the payload,
+/// payloads without one are treated as format 1.0.
+/// **http::Response** is a valid response for both formats, except for its `cookies`
+/// which format 1.0 ignores: answer `AnyRequest::V1` events setting cookies with
+/// `http::v1::Response::from(resp)`, which sends them as `Set-Cookie` multi-value headers.
+///
+/// example:
+/// ```
+/// use hegel::http;
+/// use lambda_runtime::{Error, LambdaEvent};
+///
+/// async fn handler(req: LambdaEvent<http::AnyRequest>) -> Result<http::Response, Error> {
+///     //...
+///     Ok(http::Response::new_text(req.payload.path()))
+/// }
+/// ```
+///
+/// dispatch:
+/// ```
+/// use hegel::http::{self, v1, AnyRequest};
+///
+/// // REST API proxy events have no version
+/// let mut rest = serde_json::to_value(v1::Request::default()).unwrap();
+/// rest.as_object_mut().unwrap().remove("version");
+/// assert!(matches!(serde_json::from_value(rest.clone()).unwrap(), AnyRequest::V1(_)));
+///
+/// rest["version"] = "1.0".into();
+/// let req: AnyRequest = serde_json::from_value(rest).unwrap();
+/// assert!(matches!(req, AnyRequest::V1(_)));
+/// assert_eq!(req.version(), "1.0");
+///
+/// let mut v2 = serde_json::to_value(http::Request::default()).unwrap();
+/// v2["version"] = "2.0".into();
+/// let req: AnyRequest = serde_json::from_value(v2.clone()).unwrap();
+/// assert!(matches!(req, AnyRequest::V2(_)));
+/// assert_eq!(req.version(), "2.0");
+///
+/// v2["version"] = "3.0".into();
+/// assert!(serde_json::from_value::<AnyRequest>(v2).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum AnyRequest {
+    V1(Box<v1::Request>),
+    V2(Box<Request>),
+}
+
+impl<'de> Deserialize<'de> for AnyRequest {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        match value.get("version").and_then(|v| v.as_str()) {
+            Some("2.0") => Request::deserialize(value).map(|r| AnyRequest::V2(Box::new(r))).map_err(D::Error::custom),
+            Some("1.0") | None => v1::Request::deserialize(value).map(|r| AnyRequest::V1(Box::new(r))).map_err(D::Error::custom),
+            Some(v) => Err(D::Error::custom(format!("unsupported payload format version: {}", v))),
+        }
+    }
+}
+
+impl AnyRequest {
+    /// Get payload format version
+    ///
+    /// example: `1.0`, `2.0`
+    pub fn version(&self) -> &str {
+        match self {
+            AnyRequest::V1(_) => "1.0",
+            AnyRequest::V2(_) => "2.0",
+        }
+    }
+
+    /// Get user request body as String (UTF-8)
+    pub fn body(&self) -> Result<Option<String>, ParseBodyError> {
+        match self {
+            AnyRequest::V1(r) => r.body(),
+            AnyRequest::V2(r) => r.body(),
+        }
+    }
+
+    /// Get user request body as binary
+    pub fn body_binary(&self) -> Result<Option<Vec<u8>>, base64::DecodeError> {
+        match self {
+            AnyRequest::V1(r) => r.body_binary(),
+            AnyRequest::V2(r) => r.body_binary(),
+        }
+    }
+
+    /// Get HTTP request path
+    ///
+    /// example: `/foo/bar`
+    pub fn path(&self) -> String {
+        match self {
+            AnyRequest::V1(r) => r.path(),
+            AnyRequest::V2(r) => r.path(),
+        }
+    }
+
+    /// Get user request cookies
+    pub fn cookies(&self) -> CookieJar {
+        match self {
+            AnyRequest::V1(r) => r.cookies(),
+            AnyRequest::V2(r) => r.cookies(),
+        }
+    }
+
+    /// Get user request headers
+    pub fn headers(&self) -> HeaderMap {
+        match self {
+            AnyRequest::V1(r) => r.headers(),
+            AnyRequest::V2(r) => r.headers(),
+        }
+    }
+
+    /// Get user request queries
+    pub fn queries(&self) -> Option<HashMap<String, String>> {
+        match self {
+            AnyRequest::V1(r) => r.queries(),
+            AnyRequest::V2(r) => r.queries(),
+        }
+    }
+
+    /// Get route params
+    pub fn params(&self) -> Option<HashMap<String, String>> {
+        match self {
+            AnyRequest::V1(r) => r.params(),
+            AnyRequest::V2(r) => r.params(),
+        }
+    }
+
+    /// Get API Gateway stage
+    ///
+    /// example: `$default`
+    pub fn stage(&self) -> String {
+        match self {
+            AnyRequest::V1(r) => r.stage(),
+            AnyRequest::V2(r) => r.stage(),
+        }
+    }
+
+    /// Get request datetime (Instant)
+    pub fn time(&self) -> SystemTime {
+        match self {
+            AnyRequest::V1(r) => r.time(),
+            AnyRequest::V2(r) => r.time(),
+        }
+    }
+
+    /// Get request datetime with **chrono::DateTime** type output
+    /// ! Remember to enable feature **chrono** before using it !
+    #[cfg(feature = "chrono")]
+    pub fn time_chrono(&self) -> DateTime<Utc> {
+        match self {
+            AnyRequest::V1(r) => r.time_chrono(),
+            AnyRequest::V2(r) => r.time_chrono(),
+        }
+    }
+
+    /// Get user request method
+    ///
+    /// example: `GET`, `POST`, `DELETE` ...
+    pub fn method(&self) -> String {
+        match self {
+            AnyRequest::V1(r) => r.method(),
+            AnyRequest::V2(r) => r.method(),
+        }
+    }
+
+    /// Get user request IP
+    pub fn ip(&self) -> String {
+        match self {
+            AnyRequest::V1(r) => r.ip(),
+            AnyRequest::V2(r) => r.ip(),
+        }
+    }
+
+    /// Get user request User-Agent
+    pub fn ua(&self) -> String {
+        match self {
+            AnyRequest::V1(r) => r.ua(),
+            AnyRequest::V2(r) => r.ua(),
+        }
+    }
+
+    /// Get user request HTTP protocol
+    ///
+    /// example: `HTTP/1.1`
+    pub fn protocol(&self) -> String {
+        match self {
+            AnyRequest::V1(r) => r.protocol(),
+            AnyRequest::V2(r) => r.protocol(),
+        }
+    }
+}
@@ -0,0 +1,239 @@
+//! Response body compression negotiated with `Accept-Encoding`
+//!
+//! ! Remember to enable feature **compression** before using it !
+//!
+//! example:
+//! ```
+//! use hegel::http::{HeaderMap, Response};
+//!
+//! let mut req = HeaderMap::new();
+//! req.insert("Accept-Encoding", "gzip, deflate, br;q=0.5");
+//! let resp = Response::new_json(format!("[{}]", vec!["{\"id\":42}"; 200].join(","))).compress(&req);
+//! assert!(resp.is_base64encoded);
+//! assert_eq!(resp.headers.content_encoding(), Some("gzip"));
+//! assert_eq!(resp.headers.get("vary"), Some("Accept-Encoding"));
+//! ```
+
+use std::fmt;
+use std::io::{self, Write};
+use super::{HeaderMap, Response, StatusCode};
+use super::header::add_vary;
+
+/// Bodies smaller than this number of bytes are sent as is,
+/// compressing them saves less than the headers cost
+pub const MIN_SIZE: usize = 1024;
+
+/// Content coding supported by **http::compress**
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    /// `br`
+    Brotli,
+    /// `gzip`
+    Gzip,
+    /// `deflate` (zlib format)
+    Deflate,
+}
+
+impl Encoding {
+    /// Server preference when q-values are equal
+    const PREFERRED: [Encoding; 3] = [Encoding::Brotli, Encoding::Gzip, Encoding::Deflate];
+
+    /// Get the content coding name, as used in `Content-Encoding`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Encoding::Brotli => "br",
+            Encoding::Gzip => "gzip",
+            Encoding::Deflate => "deflate",
+        }
+    }
+
+    /// Compress data with this encoding into a complete stream
+    ///
+    /// example:
+    /// ```
+    /// use std::io::Read;
+    /// use hegel::http::compress::Encoding;
+    ///
+    /// let data = "hello hegel ".repeat(500).into_bytes();
+    /// for encoding in [Encoding::Brotli, Encoding::Gzip, Encoding::Deflate] {
+    ///     let encoded = encoding.encode(&data).unwrap();
+    ///     let mut decoded = Vec::new();
+    ///     match encoding {
+    ///         Encoding::Brotli => brotli::Decompressor::new(&encoded[..], 4096).read_to_end(&mut decoded),
+    ///         Encoding::Gzip => flate2::read::GzDecoder::new(&encoded[..]).read_to_end(&mut decoded),
+    ///         Encoding::Deflate => flate2::read::ZlibDecoder::new(&encoded[..]).read_to_end(&mut decoded),
+    ///     }.unwrap();
+    ///     assert_eq!(decoded, data, "{}", encoding);
+    /// }
+    /// ```
+    pub fn encode(&self, data: &[u8]) -> io::Result<Vec<u8>> {
+        match self {
+            Encoding::Brotli => {
+                let params = brotli::enc::BrotliEncoderParams {
+                    quality: 5,
+                    lgwin: 22,
+                    ..Default::default()
+                };
+                let mut out = Vec::new();
+                brotli::BrotliCompress(&mut &data[..], &mut out, &params)?;
+                Ok(out)
+            }
+            Encoding::Gzip => {
+                let mut w = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+                w.write_all(data)?;
+                w.finish()
+            }
+            Encoding::Deflate => {
+                let mut w = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+                w.write_all(data)?;
+                w.finish()
+            }
+        }
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Pick the encoding to use from the elements of an `Accept-Encoding` header
+///
+/// The highest q-value wins, `*` covers encodings not listed and `q=0` refuses one.
+/// Equal q-values are broken with `br` > `gzip` > `deflate`.
+/// Returns `None` when no supported encoding is acceptable.
+///
+/// example:
+/// ```
+/// use hegel::http::compress::{self, Encoding};
+///
+/// assert_eq!(compress::negotiate(&["gzip", "deflate", "br"]), Some(Encoding::Brotli));
+/// assert_eq!(compress::negotiate(&["gzip;q=1.0", "br;q=0.8"]), Some(Encoding::Gzip));
+/// assert_eq!(compress::negotiate(&["*", "br;q=0"]), Some(Encoding::Gzip));
+/// assert_eq!(compress::negotiate(&["identity"]), None);
+/// assert_eq!(compress::negotiate(&[]), None);
+/// ```
+pub fn negotiate(accept_encoding: &[&str]) -> Option<Encoding> {
+    let mut listed: Vec<(Encoding, f32)> = Vec::new();
+    let mut any = None;
+    for item in accept_encoding {
+        let mut params = item.split(';');
+        let coding = params.next().unwrap_or_default().trim().to_ascii_lowercase();
+        let q = match parse_q(params) {
+            Some(q) => q,
+            None => continue,
+        };
+        match coding.as_str() {
+            "br" => listed.push((Encoding::Brotli, q)),
+            "gzip" | "x-gzip" => listed.push((Encoding::Gzip, q)),
+            "deflate" => listed.push((Encoding::Deflate, q)),
+            "*" => any = Some(q),
+            _ => (),
+        }
+    }
+    let mut best: Option<(Encoding, f32)> = None;
+    for encoding in Encoding::PREFERRED {
+        let q = listed.iter().find(|(e, _)| *e == encoding).map(|(_, q)| *q).or(any).unwrap_or(0.0);
+        if q > 0.0 && best.map(|(_, b)| q > b).unwrap_or(true) {
+            best = Some((encoding, q));
+        }
+    }
+    best.map(|(e, _)| e)
+}
+
+/// Get the q-value of an `Accept-Encoding` element,
+/// `None` when it is malformed
+fn parse_q<'a, I: Iterator<Item = &'a str>>(params: I) -> Option<f32> {
+    let mut q = 1.0;
+    for param in params {
+        if let Some((k, v)) = param.split_once('=') {
+            if k.trim().eq_ignore_ascii_case("q") {
+                q = v.trim().parse().ok().filter(|q| (0.0..=1.0).contains(q))?;
+            }
+        }
+    }
+    Some(q)
+}
+
+/// Check whether a content type is worth compressing
+///
+/// Text, JSON, XML, JavaScript and alike are, images, audio, video and archives
+/// (already compressed) are not.
+///
+/// example:
+/// ```
+/// use hegel::http::compress;
+///
+/// assert!(compress::is_compressible("application/json; charset=utf-8"));
+/// assert!(compress::is_compressible("image/svg+xml"));
+/// assert!(!compress::is_compressible("image/png"));
+/// assert!(!compress::is_compressible("text/event-stream"));
+/// ```
+pub fn is_compressible(content_type: &str) -> bool {
+    let mime = content_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+    match mime.as_str() {
+        "text/event-stream" => false,
+        m if m.starts_with("text/") => true,
+        m if m.ends_with("+json") || m.ends_with("+xml") => true,
+        "application/json"
+        | "application/javascript"
+        | "application/ecmascript"
+        | "application/xml"
+        | "application/x-ndjson"
+        | "application/yaml"
+        | "application/x-yaml"
+        | "application/graphql"
+        | "application/x-www-form-urlencoded"
+        | "application/wasm"
+        | "application/vnd.ms-fontobject"
+        | "font/ttf"
+        | "font/otf" => true,
+        _ => false,
+    }
+}
+
+pub(crate) fn apply(mut resp: Response, req: &HeaderMap) -> Response {
+    let skip_status = resp.status_code.is_informational()
+        || resp.status_code == StatusCode::NO_CONTENT
+        || resp.status_code == StatusCode::PARTIAL_CONTENT
+        || resp.status_code == StatusCode::NOT_MODIFIED;
+    if skip_status
+        || resp.headers.contains("content-encoding")
+        || resp.headers.contains("content-range")
+        || resp.headers.get_list("cache-control").iter().any(|c| c.eq_ignore_ascii_case("no-transform"))
+        || !resp.headers.content_type().map(is_compressible).unwrap_or(false)
+    {
+        return resp;
+    }
+    let body = match resp.is_base64encoded {
+        true => match base64::decode(&resp.body) {
+            Ok(b) => b,
+            Err(_) => return resp,
+        },
+        false => resp.body.as_bytes().to_vec(),
+    };
+    if body.len() < MIN_SIZE {
+        return resp;
+    }
+
+    add_vary(&mut resp.headers, &["Accept-Encoding"]);
+    let encoding = match negotiate(&req.accept_encoding()) {
+        Some(e) => e,
+        None => return resp,
+    };
+    let compressed = match encoding.encode(&body) {
+        Ok(c) if c.len() < body.len() => c,
+        _ => return resp,
+    };
+
+    // the compressed body is a different representation, keep the tag for If-None-Match only
+    if let Some(etag) = resp.headers.get("etag").filter(|e| !e.starts_with("W/")).map(|e| format!("W/{}", e)) {
+        resp.headers.insert("ETag", etag);
+    }
+    resp.headers.insert("Content-Encoding", encoding.as_str());
+    resp.headers.insert("Content-Length", compressed.len().to_string());
+    resp.body = base64::encode(compressed);
+    resp.is_base64encoded = true;
+    resp
+}
@@ -0,0 +1,118 @@
+//! Conditional requests (RFC 9110 section 13): `ETag`, `Last-Modified`,
+//! `304 Not Modified` and `412 Precondition Failed`
+//!
+//! example:
+//! ```
+//! use hegel::http::{HeaderMap, Response, StatusCode};
+//!
+//! let resp = Response::new_json(r#"{"id":42}"#.to_string()).etag();
+//! let etag = resp.headers.get("etag").unwrap().to_string();
+//!
+//! let mut req = HeaderMap::new();
+//! req.insert("If-None-Match", etag);
+//! let resp = resp.conditional(&req, "GET");
+//! assert_eq!(resp.status_code, StatusCode::NOT_MODIFIED);
+//! assert_eq!(resp.body, "");
+//! ```
+
+use std::time::SystemTime;
+use super::{HeaderMap, Response, StatusCode};
+
+/// Headers kept on `304 Not Modified` responses
+const NOT_MODIFIED_HEADERS: [&str; 7] = ["cache-control", "content-location", "date", "etag", "expires", "last-modified", "vary"];
+
+/// Compute an entity tag from body bytes, stable across runs and builds
+///
+/// example:
+/// ```
+/// use hegel::http::conditional;
+///
+/// assert_eq!(conditional::entity_tag(b"hello world"), "\"b-779a65e7023cd2e7\"");
+/// ```
+pub fn entity_tag(body: &[u8]) -> String {
+    // 64-bit FNV-1a
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for b in body {
+        hash ^= *b as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("\"{:x}-{:016x}\"", body.len(), hash)
+}
+
+/// Split an entity tag into its weakness and its opaque tag
+fn split_etag(etag: &str) -> (bool, &str) {
+    let etag = etag.trim();
+    match etag.strip_prefix("W/") {
+        Some(tag) => (true, tag),
+        None => (false, etag),
+    }
+}
+
+/// Strong comparison: both tags are strong and identical
+pub(crate) fn strong_eq(a: &str, b: &str) -> bool {
+    match (split_etag(a), split_etag(b)) {
+        ((false, a), (false, b)) => a == b,
+        _ => false,
+    }
+}
+
+/// Weak comparison: tags are identical, ignoring weakness
+pub(crate) fn weak_eq(a: &str, b: &str) -> bool {
+    split_etag(a).1 == split_etag(b).1
+}
+
+fn parse_date(date: &str) -> Option<SystemTime> {
+    httpdate::parse_http_date(date.trim()).ok()
+}
+
+pub(crate) fn apply(resp: Response, req: &HeaderMap, method: &str) -> Response {
+    if !resp.status_code.is_success() {
+        return resp;
+    }
+    let etag = resp.headers.get("etag").map(|e| e.to_string());
+    let last_modified = resp.headers.get("last-modified").and_then(parse_date);
+    let safe = method.eq_ignore_ascii_case("GET") || method.eq_ignore_ascii_case("HEAD");
+
+    // 1. If-Match, else 2. If-Unmodified-Since
+    if req.contains("if-match") {
+        let tags = req.get_list("if-match");
+        let matched = tags.iter().any(|t| *t == "*" || etag.as_deref().map(|e| strong_eq(t, e)).unwrap_or(false));
+        if !matched {
+            return Response::new_status(StatusCode::PRECONDITION_FAILED);
+        }
+    } else if let Some(since) = req.get("if-unmodified-since").and_then(parse_date) {
+        if last_modified.map(|m| m > since).unwrap_or(false) {
+            return Response::new_status(StatusCode::PRECONDITION_FAILED);
+        }
+    }
+
+    // 3. If-None-Match, else 4. If-Modified-Since
+    if req.contains("if-none-match") {
+        let tags = req.get_list("if-none-match");
+        let matched = tags.iter().any(|t| *t == "*" || etag.as_deref().map(|e| weak_eq(t, e)).unwrap_or(false));
+        if matched {
+            return match safe {
+                true => not_modified(resp),
+                false => Response::new_status(StatusCode::PRECONDITION_FAILED),
+            };
+        }
+    } else if let Some(since) = req.get("if-modified-since").and_then(parse_date).filter(|_| safe) {
+        if last_modified.map(|m| m <= since).unwrap_or(false) {
+            return not_modified(resp);
+        }
+    }
+    resp
+}
+
+fn not_modified(resp: Response) -> Response {
+    let headers = resp.headers.iter()
+        .filter(|(k, _)| NOT_MODIFIED_HEADERS.contains(&k.to_ascii_lowercase().as_str()))
+        .collect();
+    Response {
+        is_base64encoded: false,
+        status_code: StatusCode::NOT_MODIFIED,
+        body: String::new(),
+        headers,
+        cookies: resp.cookies,
+    }
+}
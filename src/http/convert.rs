@@ -0,0 +1,229 @@
+//! Conversions between **hegel::http** and the `http` crate
+//! ! Remember to enable feature **http-types** before using it !
+//!
+//! example:
+//! ```
+//! use hegel::{common, http};
+//!
+//! let mut req = http::Request {
+//!     raw_path: "/items".to_string(),
+//!     raw_query_string: "tag=a&tag=b".to_string(),
+//!     cookies: Some(vec!["session=abc".to_string()]),
+//!     ..Default::default()
+//! };
+//! req.request_context.domain_name = "api.iochen.com".to_string();
+//! req.request_context.http.method = "GET".to_string();
+//!
+//! let req: ::http::Request<Vec<u8>> = req.try_into().unwrap();
+//! assert_eq!(req.uri(), "https://api.iochen.com/items?tag=a&tag=b");
+//! assert_eq!(req.headers()["cookie"], "session=abc");
+//! assert!(req.extensions().get::<common::RequestContext>().is_some());
+//!
+//! let resp: http::Response = ::http::Response::builder()
+//!     .header("Content-Type", "image/png")
+//!     .body(vec![0x89, 0x50, 0x4e, 0x47])
+//!     .unwrap()
+//!     .into();
+//! assert!(resp.is_base64encoded);
+//! ```
+
+use super::{Request, Response, HeaderMap, Pairs, StatusCode};
+use super::utils::mime;
+use crate::common;
+use std::collections::HashMap;
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Enum type of errors that may occur when converting a **http::Request** into a `http` crate request
+#[derive(Debug)]
+pub enum ConvertError {
+    Base64DecodeError(base64::DecodeError),
+    /// The method, URI or a header is not valid
+    HttpError(::http::Error),
+}
+
+impl fmt::Display for ConvertError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConvertError::Base64DecodeError(e) => write!(f, "invalid base64 body: {}", e),
+            ConvertError::HttpError(e) => write!(f, "invalid HTTP request: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ConvertError {}
+
+impl From<::http::Error> for ConvertError {
+    fn from(e: ::http::Error) -> Self {
+        ConvertError::HttpError(e)
+    }
+}
+
+impl TryFrom<Request> for ::http::Request<Vec<u8>> {
+    type Error = ConvertError;
+
+    /// Build a `http` crate request with the URI rebuilt from the domain name,
+    /// raw path and raw query string, the cookies as a `Cookie` header,
+    /// the decoded body and the **common::RequestContext** in the extensions
+    /// ! Remember to enable feature **http-types** before using it !
+    fn try_from(req: Request) -> Result<Self, Self::Error> {
+        let body = req.body_binary()
+            .map_err(ConvertError::Base64DecodeError)?
+            .unwrap_or_default();
+
+        let mut uri = String::new();
+        if !req.request_context.domain_name.is_empty() {
+            uri.push_str("https://");
+            uri.push_str(&req.request_context.domain_name);
+        }
+        uri.push_str(if req.raw_path.is_empty() { "/" } else { &req.raw_path });
+        if !req.raw_query_string.is_empty() {
+            uri.push('?');
+            uri.push_str(&req.raw_query_string);
+        }
+
+        let version = match req.request_context.http.protocol.as_str() {
+            "HTTP/1.0" => ::http::Version::HTTP_10,
+            "HTTP/2" | "HTTP/2.0" => ::http::Version::HTTP_2,
+            "HTTP/3" | "HTTP/3.0" => ::http::Version::HTTP_3,
+            _ => ::http::Version::HTTP_11,
+        };
+
+        let mut builder = ::http::Request::builder()
+            .method(req.request_context.http.method.as_str())
+            .uri(uri)
+            .version(version);
+        for (k, v) in req.headers.iter() {
+            builder = builder.header(k, v);
+        }
+        if let Some(cookies) = req.cookies.as_ref().filter(|c| !c.is_empty()) {
+            builder = builder.header(::http::header::COOKIE, cookies.join("; "));
+        }
+        Ok(builder.extension(req.request_context).body(body)?)
+    }
+}
+
+impl<B: AsRef<[u8]>> From<::http::Request<B>> for Request {
+    /// Build a payload format 2.0 request from a `http` crate request
+    ///
+    /// The **common::RequestContext** is taken from the extensions when present,
+    /// otherwise it is filled from the request. Bodies which are not UTF-8 are base64 encoded.
+    /// ! Remember to enable feature **http-types** before using it !
+    fn from(req: ::http::Request<B>) -> Self {
+        let (parts, body) = req.into_parts();
+        let mut headers = HeaderMap::new();
+        let mut cookies = Vec::new();
+        for (k, v) in parts.headers.iter() {
+            let v = String::from_utf8_lossy(v.as_bytes()).into_owned();
+            if k == ::http::header::COOKIE {
+                cookies.extend(v.split(';').map(|c| c.trim().to_string()).filter(|c| !c.is_empty()));
+            } else {
+                headers.append(k.as_str(), v);
+            }
+        }
+
+        let raw_query_string = parts.uri.query().unwrap_or_default().to_string();
+        let mut queries: HashMap<String, String> = HashMap::new();
+        for (k, v) in Pairs::parse(raw_query_string.as_bytes()) {
+            queries.entry(k)
+                .and_modify(|q| {
+                    q.push(',');
+                    q.push_str(&v);
+                })
+                .or_insert(v);
+        }
+
+        let request_context = match parts.extensions.get::<common::RequestContext>() {
+            Some(ctx) => ctx.clone(),
+            None => common::RequestContext {
+                domain_name: parts.uri.host().or_else(|| headers.host()).unwrap_or_default().to_string(),
+                http: common::Http {
+                    method: parts.method.to_string(),
+                    path: parts.uri.path().to_string(),
+                    protocol: format!("{:?}", parts.version),
+                    source_ip: String::new(),
+                    user_agent: headers.user_agent().unwrap_or_default().to_string(),
+                },
+                route_key: "$default".to_string(),
+                stage: "$default".to_string(),
+                time_epoch: SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or_default(),
+                ..Default::default()
+            },
+        };
+
+        let body = body.as_ref();
+        let (body, is_base64encoded) = match std::str::from_utf8(body) {
+            _ if body.is_empty() => (None, false),
+            Ok(text) => (Some(text.to_string()), false),
+            Err(_) => (Some(base64::encode(body)), true),
+        };
+
+        Request {
+            version: "2.0".to_string(),
+            route_key: "$default".to_string(),
+            raw_path: parts.uri.path().to_string(),
+            raw_query_string,
+            cookies: if cookies.is_empty() { None } else { Some(cookies) },
+            headers,
+            query_string_parameters: if queries.is_empty() { None } else { Some(queries) },
+            request_context,
+            body,
+            path_parameters: None,
+            is_base64encoded,
+            stage_variables: None,
+        }
+    }
+}
+
+impl TryFrom<Response> for ::http::Response<Vec<u8>> {
+    type Error = ConvertError;
+
+    /// Build a `http` crate response with the decoded body and cookies as `Set-Cookie` headers
+    /// ! Remember to enable feature **http-types** before using it !
+    fn try_from(resp: Response) -> Result<Self, Self::Error> {
+        let body = if resp.is_base64encoded {
+            base64::decode(&resp.body).map_err(ConvertError::Base64DecodeError)?
+        } else {
+            resp.body.into_bytes()
+        };
+        let mut builder = ::http::Response::builder().status(resp.status_code.as_u16());
+        for (k, v) in resp.headers.iter() {
+            builder = builder.header(k, v);
+        }
+        for c in &resp.cookies {
+            builder = builder.header(::http::header::SET_COOKIE, c);
+        }
+        Ok(builder.body(body)?)
+    }
+}
+
+impl<B: AsRef<[u8]>> From<::http::Response<B>> for Response {
+    /// Take a `http` crate response, `Set-Cookie` headers become cookies and
+    /// bodies which are not UTF-8 text are base64 encoded
+    /// ! Remember to enable feature **http-types** before using it !
+    fn from(resp: ::http::Response<B>) -> Self {
+        let (parts, body) = resp.into_parts();
+        let mut r = Response {
+            status_code: StatusCode::try_from(parts.status.as_u16()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+            ..Default::default()
+        };
+        for (k, v) in parts.headers.iter() {
+            let v = String::from_utf8_lossy(v.as_bytes()).into_owned();
+            if k == ::http::header::SET_COOKIE {
+                r.cookies.push(v);
+            } else {
+                r.headers.append(k.as_str(), v);
+            }
+        }
+        let body = body.as_ref();
+        let textual = r.headers.content_type().map(mime::is_text).unwrap_or(true);
+        match std::str::from_utf8(body) {
+            Ok(text) if textual => r.body = text.to_string(),
+            _ => {
+                r.body = base64::encode(body);
+                r.is_base64encoded = true;
+            }
+        }
+        r
+    }
+}
@@ -0,0 +1,316 @@
+use std::fmt;
+use crate::common;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// `SameSite` attribute of a cookie
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SameSite {
+    Strict,
+    Lax,
+    /// Browsers only accept it on `Secure` cookies
+    None,
+}
+
+impl fmt::Display for SameSite {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SameSite::Strict => write!(f, "Strict"),
+            SameSite::Lax => write!(f, "Lax"),
+            SameSite::None => write!(f, "None"),
+        }
+    }
+}
+
+/// Cookie sent to the user with a **http::Response**
+///
+/// Characters not allowed in a cookie value (like `;`, `,`, spaces and `%`)
+/// are percent-encoded when the cookie is written, and so are the ones not allowed
+/// in the name, `Domain` and `Path` (like `;`, `,` and line breaks), so they can not
+/// add attributes. `Expires` is clamped to the years 1970 to 9999.
+///
+/// example:
+/// ```
+/// use std::time::Duration;
+/// use hegel::http::{Response, StatusCode, SetCookie, SameSite};
+///
+/// let cookie = SetCookie::new("session".to_string(), "a1b2==".to_string())
+///     .path("/".to_string())
+///     .max_age(Duration::from_secs(3600))
+///     .secure(true)
+///     .http_only(true)
+///     .same_site(SameSite::Lax);
+/// assert_eq!(
+///     cookie.to_string(),
+///     "session=a1b2==; Path=/; Max-Age=3600; Secure; HttpOnly; SameSite=Lax"
+/// );
+///
+/// let resp = Response::new_status(StatusCode::OK)
+///     .cookie(cookie)
+///     .cookie(SetCookie::new("theme".to_string(), "dark".to_string()));
+/// assert_eq!(resp.cookies.len(), 2);
+///
+/// let cookie = SetCookie::new("id; Secure".to_string(), "1".to_string())
+///     .path("/; Domain=evil.com\r\n".to_string())
+///     .expires(std::time::UNIX_EPOCH - Duration::from_secs(60));
+/// assert_eq!(
+///     cookie.to_string(),
+///     "id%3B%20Secure=1; Path=/%3B Domain=evil.com%0D%0A; Expires=Thu, 01 Jan 1970 00:00:00 GMT"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetCookie {
+    pub name: String,
+    pub value: String,
+    pub domain: Option<String>,
+    pub path: Option<String>,
+    pub expires: Option<SystemTime>,
+    pub max_age: Option<Duration>,
+    pub secure: bool,
+    pub http_only: bool,
+    pub same_site: Option<SameSite>,
+    /// Browsers only accept it on `Secure` cookies
+    pub partitioned: bool,
+}
+
+impl SetCookie {
+    /// new session **SetCookie** without attributes
+    pub fn new(name: String, value: String) -> SetCookie {
+        SetCookie {
+            name,
+            value,
+            domain: None,
+            path: None,
+            expires: None,
+            max_age: None,
+            secure: false,
+            http_only: false,
+            same_site: None,
+            partitioned: false,
+        }
+    }
+
+    /// return a SetCookie with provided `Domain` attribute
+    /// like s struct builder
+    pub fn domain(mut self, domain: String) -> SetCookie {
+        self.domain = Some(domain);
+        self
+    }
+
+    /// return a SetCookie with provided `Path` attribute
+    /// like s struct builder
+    pub fn path(mut self, path: String) -> SetCookie {
+        self.path = Some(path);
+        self
+    }
+
+    /// return a SetCookie with provided `Expires` attribute
+    /// like s struct builder
+    pub fn expires(mut self, expires: SystemTime) -> SetCookie {
+        self.expires = Some(expires);
+        self
+    }
+
+    /// return a SetCookie with provided `Max-Age` attribute (in whole seconds)
+    /// like s struct builder
+    pub fn max_age(mut self, max_age: Duration) -> SetCookie {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// return a SetCookie with `Secure` attribute set or unset
+    /// like s struct builder
+    pub fn secure(mut self, secure: bool) -> SetCookie {
+        self.secure = secure;
+        self
+    }
+
+    /// return a SetCookie with `HttpOnly` attribute set or unset
+    /// like s struct builder
+    pub fn http_only(mut self, http_only: bool) -> SetCookie {
+        self.http_only = http_only;
+        self
+    }
+
+    /// return a SetCookie with provided `SameSite` attribute
+    /// like s struct builder
+    pub fn same_site(mut self, same_site: SameSite) -> SetCookie {
+        self.same_site = Some(same_site);
+        self
+    }
+
+    /// return a SetCookie with `Partitioned` attribute set or unset
+    /// like s struct builder
+    pub fn partitioned(mut self, partitioned: bool) -> SetCookie {
+        self.partitioned = partitioned;
+        self
+    }
+
+    /// return a SetCookie telling the browser to delete the cookie immediately
+    /// like s struct builder
+    ///
+    /// `Domain` and `Path` must match the ones the cookie was set with
+    pub fn expired(mut self) -> SetCookie {
+        self.value = String::new();
+        self.max_age = Some(Duration::ZERO);
+        self.expires = Some(UNIX_EPOCH);
+        self
+    }
+}
+
+impl fmt::Display for SetCookie {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_escaped(f, &self.name, is_token_char)?;
+        write!(f, "=")?;
+        write_escaped(f, &self.value, is_cookie_octet)?;
+        if let Some(domain) = &self.domain {
+            write!(f, "; Domain=")?;
+            write_escaped(f, domain, is_attribute_char)?;
+        }
+        if let Some(path) = &self.path {
+            write!(f, "; Path=")?;
+            write_escaped(f, path, is_attribute_char)?;
+        }
+        if let Some(expires) = self.expires {
+            write!(f, "; Expires={}", common::fmt_http_date(expires))?;
+        }
+        if let Some(max_age) = self.max_age {
+            write!(f, "; Max-Age={}", max_age.as_secs())?;
+        }
+        if self.secure {
+            write!(f, "; Secure")?;
+        }
+        if self.http_only {
+            write!(f, "; HttpOnly")?;
+        }
+        if let Some(same_site) = self.same_site {
+            write!(f, "; SameSite={}", same_site)?;
+        }
+        if self.partitioned {
+            write!(f, "; Partitioned")?;
+        }
+        Ok(())
+    }
+}
+
+/// Write a string, percent-encoding the bytes not allowed
+fn write_escaped(f: &mut fmt::Formatter<'_>, s: &str, allowed: fn(u8) -> bool) -> fmt::Result {
+    for b in s.bytes() {
+        if allowed(b) {
+            write!(f, "{}", b as char)?;
+        } else {
+            write!(f, "%{:02X}", b)?;
+        }
+    }
+    Ok(())
+}
+
+/// `tchar` of RFC 9110 (cookie names), except `%` which is kept for percent-encoding
+fn is_token_char(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b"!#$&'*+-.^_`|~".contains(&b)
+}
+
+/// Attribute value characters of RFC 6265 except `,`,
+/// `%` is kept for percent-encoding
+fn is_attribute_char(b: u8) -> bool {
+    matches!(b, 0x20..=0x7E) && !matches!(b, b';' | b',' | b'%')
+}
+
+/// `cookie-octet` of RFC 6265, except `%` which is kept for percent-encoding
+fn is_cookie_octet(b: u8) -> bool {
+    matches!(b, 0x21 | 0x23..=0x24 | 0x26..=0x2B | 0x2D..=0x3A | 0x3C..=0x5B | 0x5D..=0x7E)
+}
+
+/// Cookies sent by the user, parsed from `Cookie` headers (RFC 6265)
+///
+/// Values may contain `=`, surrounding double quotes are removed and
+/// `%XX` escapes are decoded. Cookies sharing a name are all kept, in the
+/// order the user sent them.
+///
+/// example:
+/// ```
+/// use hegel::http::CookieJar;
+///
+/// let jar = CookieJar::parse(["token=eyJhbGciOi==; theme=\"dark\"", "id=1; id=2; name=J%C3%BCrgen"]);
+/// assert_eq!(jar.get("token"), Some("eyJhbGciOi=="));
+/// assert_eq!(jar.get("theme"), Some("dark"));
+/// assert_eq!(jar.get_all("id"), vec!["1", "2"]);
+/// assert_eq!(jar.get("name"), Some("Jürgen"));
+/// assert_eq!(jar.iter().count(), 5);
+/// ```
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct CookieJar {
+    cookies: Vec<(String, String)>,
+}
+
+impl CookieJar {
+    /// Parse cookie strings, each one being a `Cookie` header value
+    /// or a single `name=value` pair
+    pub fn parse<'a, I>(cookie_strings: I) -> CookieJar
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let mut cookies = Vec::new();
+        for s in cookie_strings {
+            for pair in s.split(';') {
+                let (name, value) = match pair.split_once('=') {
+                    Some(p) => p,
+                    None => continue,
+                };
+                let name = name.trim();
+                if name.is_empty() {
+                    continue;
+                }
+                let value = value.trim();
+                let value = value.strip_prefix('"')
+                    .and_then(|v| v.strip_suffix('"'))
+                    .unwrap_or(value);
+                cookies.push((name.to_string(), common::percent_decode(value)));
+            }
+        }
+        CookieJar { cookies }
+    }
+
+    /// Get the value of the first cookie with provided name
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.cookies.iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Get the values of all cookies with provided name
+    pub fn get_all(&self, name: &str) -> Vec<&str> {
+        self.cookies.iter()
+            .filter(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+            .collect()
+    }
+
+    /// Check whether a cookie with provided name was sent
+    pub fn contains(&self, name: &str) -> bool {
+        self.cookies.iter().any(|(n, _)| n == name)
+    }
+
+    /// Iterate over all cookies as `(name, value)`
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.cookies.iter().map(|(n, v)| (n.as_str(), v.as_str()))
+    }
+
+    /// Get the number of cookies
+    pub fn len(&self) -> usize {
+        self.cookies.len()
+    }
+
+    /// Check whether no cookie was sent
+    pub fn is_empty(&self) -> bool {
+        self.cookies.is_empty()
+    }
+}
+
+impl IntoIterator for CookieJar {
+    type Item = (String, String);
+    type IntoIter = std::vec::IntoIter<(String, String)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cookies.into_iter()
+    }
+}
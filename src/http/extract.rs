@@ -0,0 +1,473 @@
+//! Extractors pulling typed values out of **http::Request**,
+//! used as handler arguments with **hegel::router::Router**
+//!
+//! example:
+//! ```
+//! use hegel::common::{Authorizer, RequestContext};
+//! use hegel::http::{HeaderMap, IntoResponse};
+//! use hegel::router::Router;
+//!
+//! async fn get_secret(_: Authorizer, headers: HeaderMap, ctx: RequestContext) -> impl IntoResponse {
+//!     format!("secret for {} in {}", headers.user_agent().unwrap_or("?"), ctx.stage)
+//! }
+//!
+//! let router = Router::new().route("GET /secret", get_secret);
+//!
+//! # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+//! let mut req = hegel::http::Request::default();
+//! req.request_context.http.method = "GET".to_string();
+//! req.request_context.http.path = "/secret".to_string();
+//! let resp = router.handle(lambda_runtime::LambdaEvent::new(req, Default::default())).await.unwrap();
+//! assert_eq!(resp.status_code, 401);
+//! # });
+//! ```
+//!
+//! `Path`, `Query`, `Form`, `StageVariables` and `AuthorizerContext` deserialize
+//! urlencoded values and need feature **form**, `Json` needs feature **json**.
+//!
+//! When an extractor fails the handler is not called,
+//! the **Rejection** is answered instead (`400`, `401`, `415` or `422`).
+
+use std::fmt;
+use crate::common;
+use super::{Request, Response, IntoResponse, StatusCode, HeaderMap, CookieJar};
+
+#[cfg(any(feature = "form", feature = "json"))]
+use serde::de::DeserializeOwned;
+
+#[cfg(feature = "form")]
+use std::collections::HashMap;
+#[cfg(feature = "form")]
+use super::{FormError, QueryError};
+#[cfg(feature = "form")]
+use serde::de::{self, IntoDeserializer, Visitor};
+#[cfg(feature = "form")]
+use crate::router::RouteKey;
+
+#[cfg(feature = "json")]
+use serde::Serialize;
+#[cfg(feature = "json")]
+use super::JsonError;
+
+/// Types which can be extracted from a request
+///
+/// example:
+/// ```
+/// use hegel::http::{Request, extract::{FromRequest, Rejection}};
+///
+/// struct ApiKey(String);
+///
+/// impl FromRequest for ApiKey {
+///     type Rejection = Rejection;
+///
+///     fn from_request(req: &Request, _: &lambda_runtime::Context) -> Result<Self, Rejection> {
+///         req.headers.get("x-api-key")
+///             .map(|k| ApiKey(k.to_string()))
+///             .ok_or_else(|| Rejection::MissingHeader("x-api-key".to_string()))
+///     }
+/// }
+/// ```
+pub trait FromRequest: Sized {
+    /// Answer sent when the extraction fails
+    type Rejection: IntoResponse;
+
+    /// Extract it from the request
+    fn from_request(req: &Request, ctx: &lambda_runtime::Context) -> Result<Self, Self::Rejection>;
+}
+
+/// Enum type of errors that may occur during extraction
+#[derive(Debug)]
+pub enum Rejection {
+    /// Path params do not match the target type (`400`)
+    #[cfg(feature = "form")]
+    Path(serde_html_form::de::Error),
+    /// Query string does not match the target type (`400`)
+    #[cfg(feature = "form")]
+    Query(QueryError),
+    /// Form body can not be extracted (`400`, `415` or `422`)
+    #[cfg(feature = "form")]
+    Form(FormError),
+    /// JSON body can not be extracted (`400`, `415` or `422`)
+    #[cfg(feature = "json")]
+    Json(JsonError),
+    /// A required header is missing (`400`)
+    MissingHeader(String),
+    /// Stage variables do not match the target type (`400`)
+    #[cfg(feature = "form")]
+    StageVariables(serde_html_form::de::Error),
+    /// The request was not authorized by an authorizer (`401`)
+    MissingAuthorizer,
+    /// Lambda authorizer context does not match the target type (`400`)
+    #[cfg(feature = "form")]
+    AuthorizerContext(serde_html_form::de::Error),
+}
+
+impl Rejection {
+    /// Get the HTTP status code answered for this rejection
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            #[cfg(feature = "form")]
+            Rejection::Form(FormError::UnsupportedContentType(_)) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            #[cfg(feature = "form")]
+            Rejection::Form(FormError::DeserializeError(_)) => StatusCode::UNPROCESSABLE_ENTITY,
+            #[cfg(feature = "json")]
+            Rejection::Json(JsonError::UnsupportedContentType(_)) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            #[cfg(feature = "json")]
+            Rejection::Json(JsonError::DeserializeError(e)) => match e.inner().classify() {
+                serde_json::error::Category::Data => StatusCode::UNPROCESSABLE_ENTITY,
+                _ => StatusCode::BAD_REQUEST,
+            },
+            Rejection::MissingAuthorizer => StatusCode::UNAUTHORIZED,
+            _ => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            #[cfg(feature = "form")]
+            Rejection::Path(e) => write!(f, "invalid path params: {}", e),
+            #[cfg(feature = "form")]
+            Rejection::Query(e) => write!(f, "{}", e),
+            #[cfg(feature = "form")]
+            Rejection::Form(e) => write!(f, "{}", e),
+            #[cfg(feature = "json")]
+            Rejection::Json(e) => write!(f, "{}", e),
+            Rejection::MissingHeader(h) => write!(f, "missing header {}", h),
+            #[cfg(feature = "form")]
+            Rejection::StageVariables(e) => write!(f, "invalid stage variables: {}", e),
+            Rejection::MissingAuthorizer => write!(f, "request is not authorized"),
+            #[cfg(feature = "form")]
+            Rejection::AuthorizerContext(e) => write!(f, "invalid authorizer context: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for Rejection {}
+
+/// text(UTF-8) Response with the rejection message
+impl IntoResponse for Rejection {
+    fn into_response(self) -> Response {
+        Response::new_status(self.status_code()).body_text(self.to_string())
+    }
+}
+
+/// Deserialize string pairs with the same rules as query strings,
+/// so `Path<u64>` or `{ id: u64 }` fields work on string values
+///
+/// Targets which are not structs or maps get the values alone, in the order of the pairs.
+#[cfg(feature = "form")]
+fn from_pairs<'a, T, I>(pairs: I) -> Result<T, serde_html_form::de::Error>
+where
+    T: DeserializeOwned,
+    I: IntoIterator<Item = (&'a String, &'a String)> + Clone,
+{
+    let encoded = form_urlencoded::Serializer::new(String::new()).extend_pairs(pairs.clone()).finish();
+    match serde_html_form::from_str(&encoded) {
+        Ok(v) => Ok(v),
+        Err(e) => {
+            let values: Vec<ParamValue> = pairs.into_iter().map(|(_, v)| ParamValue(v.clone())).collect();
+            let result = match values.len() {
+                1 => T::deserialize(values.into_iter().next().unwrap_or_default()),
+                _ => T::deserialize(de::value::SeqDeserializer::new(values.into_iter())),
+            };
+            result.map_err(|_| e)
+        }
+    }
+}
+
+/// A single param value, parsed when the target is a number or a boolean
+#[cfg(feature = "form")]
+#[derive(Default)]
+struct ParamValue(String);
+
+#[cfg(feature = "form")]
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                match self.0.parse() {
+                    Ok(v) => visitor.$visit(v),
+                    Err(_) => Err(de::Error::invalid_value(de::Unexpected::Str(&self.0), &visitor)),
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "form")]
+impl<'de> de::Deserializer<'de> for ParamValue {
+    type Error = serde_html_form::de::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_string(self.0)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _: &'static str, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_seq(de::value::SeqDeserializer::new(std::iter::once(self)))
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, _: usize, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_enum(IntoDeserializer::<Self::Error>::into_deserializer(self.0))
+    }
+
+    deserialize_parsed! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+    }
+
+    serde::forward_to_deserialize_any! {
+        char str string bytes byte_buf unit unit_struct tuple_struct map struct identifier ignored_any
+    }
+}
+
+#[cfg(feature = "form")]
+impl<'de> IntoDeserializer<'de, serde_html_form::de::Error> for ParamValue {
+    type Deserializer = ParamValue;
+
+    fn into_deserializer(self) -> ParamValue {
+        self
+    }
+}
+
+/// Extract route params into a struct, a single value when the route has one param,
+/// or a tuple of the params in route order
+/// ! Remember to enable feature **form** before using it !
+///
+/// example:
+/// ```
+/// use hegel::http::extract::{FromRequest, Path};
+///
+/// async fn get_item(Path(id): Path<u64>) -> String {
+///     format!("item {}", id)
+/// }
+///
+/// let mut req = hegel::http::Request::default();
+/// req.route_key = "GET /users/{user}/posts/{post}".to_string();
+/// req.path_parameters = Some([("user", "alice"), ("post", "42")].iter()
+///     .map(|(k, v)| (k.to_string(), v.to_string()))
+///     .collect());
+/// let Path((user, post)) = Path::<(String, u64)>::from_request(&req, &Default::default()).unwrap();
+/// assert_eq!((user.as_str(), post), ("alice", 42));
+/// ```
+#[cfg(feature = "form")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Path<T>(pub T);
+
+#[cfg(feature = "form")]
+impl<T: DeserializeOwned> FromRequest for Path<T> {
+    type Rejection = Rejection;
+
+    fn from_request(req: &Request, _: &lambda_runtime::Context) -> Result<Self, Rejection> {
+        let empty = HashMap::new();
+        let key: Option<RouteKey> = req.route_key.parse().ok();
+        let names = key.as_ref().map(|k| k.param_names()).unwrap_or_default();
+        let mut params: Vec<_> = req.path_parameters.as_ref().unwrap_or(&empty).iter().collect();
+        params.sort_by_key(|(k, _)| (names.iter().position(|n| n == k).unwrap_or(names.len()), k.as_str()));
+        from_pairs(params).map(Path).map_err(Rejection::Path)
+    }
+}
+
+/// Extract the query string with **http::Request::query**
+/// ! Remember to enable feature **form** before using it !
+#[cfg(feature = "form")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Query<T>(pub T);
+
+#[cfg(feature = "form")]
+impl<T: DeserializeOwned> FromRequest for Query<T> {
+    type Rejection = Rejection;
+
+    fn from_request(req: &Request, _: &lambda_runtime::Context) -> Result<Self, Rejection> {
+        req.query().map(Query).map_err(Rejection::Query)
+    }
+}
+
+/// Extract an `application/x-www-form-urlencoded` body with **http::Request::form**
+/// ! Remember to enable feature **form** before using it !
+#[cfg(feature = "form")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Form<T>(pub T);
+
+#[cfg(feature = "form")]
+impl<T: DeserializeOwned> FromRequest for Form<T> {
+    type Rejection = Rejection;
+
+    fn from_request(req: &Request, _: &lambda_runtime::Context) -> Result<Self, Rejection> {
+        req.form().map(Form).map_err(Rejection::Form)
+    }
+}
+
+/// Extract a JSON body with **http::Request::json**,
+/// or answer a value serialized to JSON
+/// ! Remember to enable feature **json** before using it !
+///
+/// example:
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use hegel::http::extract::Json;
+///
+/// #[derive(Deserialize, Serialize)]
+/// struct Item {
+///     name: String,
+/// }
+///
+/// async fn put_item(Json(item): Json<Item>) -> Json<Item> {
+///     Json(item)
+/// }
+/// ```
+#[cfg(feature = "json")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Json<T>(pub T);
+
+#[cfg(feature = "json")]
+impl<T: DeserializeOwned> FromRequest for Json<T> {
+    type Rejection = Rejection;
+
+    fn from_request(req: &Request, _: &lambda_runtime::Context) -> Result<Self, Rejection> {
+        req.json().map(Json).map_err(Rejection::Json)
+    }
+}
+
+/// JSON Response, or `500` when the value can not be serialized
+#[cfg(feature = "json")]
+impl<T: Serialize> IntoResponse for Json<T> {
+    fn into_response(self) -> Response {
+        match Response::json(&self.0) {
+            Ok(resp) => resp,
+            Err(e) => Response::new_status(StatusCode::INTERNAL_SERVER_ERROR).body_text(e.to_string()),
+        }
+    }
+}
+
+/// Extract stage variables into a struct, all of them by default
+/// ! Remember to enable feature **form** before using it !
+#[cfg(feature = "form")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StageVariables<T = HashMap<String, String>>(pub T);
+
+#[cfg(feature = "form")]
+impl<T: DeserializeOwned> FromRequest for StageVariables<T> {
+    type Rejection = Rejection;
+
+    fn from_request(req: &Request, _: &lambda_runtime::Context) -> Result<Self, Rejection> {
+        let empty = HashMap::new();
+        let vars = req.stage_variables.as_ref().unwrap_or(&empty);
+        from_pairs(vars).map(StageVariables).map_err(Rejection::StageVariables)
+    }
+}
+
+/// Extract the context returned by a Lambda authorizer into a struct
+///
+/// Answers `401` when the request was not authorized by a Lambda authorizer.
+/// ! Remember to enable feature **form** before using it !
+#[cfg(feature = "form")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthorizerContext<T = HashMap<String, String>>(pub T);
+
+#[cfg(feature = "form")]
+impl<T: DeserializeOwned> FromRequest for AuthorizerContext<T> {
+    type Rejection = Rejection;
+
+    fn from_request(req: &Request, _: &lambda_runtime::Context) -> Result<Self, Rejection> {
+        let context = req.request_context.authorizer.as_ref()
+            .and_then(|a| a.lambda.as_ref())
+            .ok_or(Rejection::MissingAuthorizer)?;
+        from_pairs(context).map(AuthorizerContext).map_err(Rejection::AuthorizerContext)
+    }
+}
+
+/// Extract the authorizer output, answers `401` when there is none
+impl FromRequest for common::Authorizer {
+    type Rejection = Rejection;
+
+    fn from_request(req: &Request, _: &lambda_runtime::Context) -> Result<Self, Rejection> {
+        req.request_context.authorizer.clone().ok_or(Rejection::MissingAuthorizer)
+    }
+}
+
+impl FromRequest for common::RequestContext {
+    type Rejection = Rejection;
+
+    fn from_request(req: &Request, _: &lambda_runtime::Context) -> Result<Self, Rejection> {
+        Ok(req.request_context.clone())
+    }
+}
+
+impl FromRequest for lambda_runtime::Context {
+    type Rejection = Rejection;
+
+    fn from_request(_: &Request, ctx: &lambda_runtime::Context) -> Result<Self, Rejection> {
+        Ok(ctx.clone())
+    }
+}
+
+impl FromRequest for HeaderMap {
+    type Rejection = Rejection;
+
+    fn from_request(req: &Request, _: &lambda_runtime::Context) -> Result<Self, Rejection> {
+        Ok(req.headers())
+    }
+}
+
+impl FromRequest for CookieJar {
+    type Rejection = Rejection;
+
+    fn from_request(req: &Request, _: &lambda_runtime::Context) -> Result<Self, Rejection> {
+        Ok(req.cookies())
+    }
+}
+
+/// The whole request
+impl FromRequest for Request {
+    type Rejection = Rejection;
+
+    fn from_request(req: &Request, _: &lambda_runtime::Context) -> Result<Self, Rejection> {
+        Ok(req.clone())
+    }
+}
+
+/// `None` instead of a rejection
+impl<T: FromRequest> FromRequest for Option<T> {
+    type Rejection = Rejection;
+
+    fn from_request(req: &Request, ctx: &lambda_runtime::Context) -> Result<Self, Rejection> {
+        Ok(T::from_request(req, ctx).ok())
+    }
+}
+
+/// The rejection instead of answering it
+impl<T: FromRequest> FromRequest for Result<T, T::Rejection> {
+    type Rejection = Rejection;
+
+    fn from_request(req: &Request, ctx: &lambda_runtime::Context) -> Result<Self, Rejection> {
+        Ok(T::from_request(req, ctx))
+    }
+}
@@ -0,0 +1,63 @@
+use std::fmt;
+use serde::de::DeserializeOwned;
+use super::urlencoded::Pairs;
+use crate::common;
+use super::header::HeaderMap;
+
+/// Enum type of errors that may occur during form request body extraction
+#[derive(Debug, Clone)]
+pub enum FormError {
+    /// The request has no body
+    MissingBody,
+    /// The `Content-Type` header is missing (`None`) or not `application/x-www-form-urlencoded`
+    UnsupportedContentType(Option<String>),
+    Base64DecodeError(base64::DecodeError),
+    /// The body does not match the target type
+    DeserializeError(serde_html_form::de::Error),
+}
+
+impl fmt::Display for FormError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormError::MissingBody => write!(f, "request body is missing"),
+            FormError::UnsupportedContentType(None) => write!(f, "Content-Type header is missing, expected {}", FORM_MIME),
+            FormError::UnsupportedContentType(Some(t)) => write!(f, "unsupported Content-Type {}, expected {}", t, FORM_MIME),
+            FormError::Base64DecodeError(e) => write!(f, "invalid base64 body: {}", e),
+            FormError::DeserializeError(e) => write!(f, "invalid form body: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for FormError {}
+
+const FORM_MIME: &str = "application/x-www-form-urlencoded";
+
+fn form_body(
+    headers: &HeaderMap,
+    body: &Option<String>,
+    is_base64encoded: bool,
+) -> Result<Vec<u8>, FormError> {
+    if headers.mime_type().as_deref() != Some(FORM_MIME) {
+        return Err(FormError::UnsupportedContentType(headers.content_type().map(|t| t.to_string())));
+    }
+    common::decode_body_binary(body, is_base64encoded)
+        .map_err(FormError::Base64DecodeError)?
+        .ok_or(FormError::MissingBody)
+}
+
+pub(crate) fn pairs_from_body(
+    headers: &HeaderMap,
+    body: &Option<String>,
+    is_base64encoded: bool,
+) -> Result<Pairs, FormError> {
+    Ok(Pairs::parse(&form_body(headers, body, is_base64encoded)?))
+}
+
+pub(crate) fn from_body<T: DeserializeOwned>(
+    headers: &HeaderMap,
+    body: &Option<String>,
+    is_base64encoded: bool,
+) -> Result<T, FormError> {
+    let body = form_body(headers, body, is_base64encoded)?;
+    serde_html_form::from_bytes(&body).map_err(FormError::DeserializeError)
+}
@@ -0,0 +1,276 @@
+use std::collections::{BTreeMap, HashMap};
+use serde::{Serialize, Serializer, Deserialize, Deserializer};
+use serde::ser::SerializeMap;
+
+/// HTTP headers with case-insensitive names and multiple values per name
+///
+/// API Gateway joins repeated request headers with commas, `get_list` splits
+/// them again. Names keep the casing they were first inserted with and are
+/// serialized in alphabetical order, values of a name joined with `, `.
+///
+/// example:
+/// ```
+/// use hegel::http::HeaderMap;
+///
+/// let mut headers = HeaderMap::new();
+/// headers.insert("Content-Type", "application/json");
+/// headers.insert("content-type", "text/plain");
+/// headers.append("Vary", "Origin");
+/// headers.append("vary", "Accept-Encoding");
+/// assert_eq!(headers.content_type(), Some("text/plain"));
+/// assert_eq!(headers.get_all("VARY"), vec!["Origin", "Accept-Encoding"]);
+/// assert_eq!(
+///     serde_json::to_string(&headers).unwrap(),
+///     r#"{"Content-Type":"text/plain","Vary":"Origin, Accept-Encoding"}"#
+/// );
+///
+/// let headers: HeaderMap = serde_json::from_str(r#"{"accept":"text/html, application/json;q=0.9"}"#).unwrap();
+/// assert_eq!(headers.get_list("Accept"), vec!["text/html", "application/json;q=0.9"]);
+/// ```
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct HeaderMap {
+    entries: BTreeMap<String, Entry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Entry {
+    name: String,
+    values: Vec<String>,
+}
+
+impl HeaderMap {
+    /// new empty **HeaderMap**
+    pub fn new() -> HeaderMap {
+        HeaderMap::default()
+    }
+
+    /// Get the first value of the header with provided name
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.entries.get(&name.to_ascii_lowercase())
+            .and_then(|e| e.values.first())
+            .map(|v| v.as_str())
+    }
+
+    /// Get all values of the header with provided name
+    pub fn get_all(&self, name: &str) -> Vec<&str> {
+        self.entries.get(&name.to_ascii_lowercase())
+            .map(|e| e.values.iter().map(|v| v.as_str()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Get all elements of a comma-separated list header with provided name
+    /// (like `Accept`, `Accept-Encoding` or `If-None-Match`), across all its values
+    ///
+    /// Commas inside quoted strings do not split elements
+    pub fn get_list(&self, name: &str) -> Vec<&str> {
+        self.get_all(name).into_iter().flat_map(split_list).collect()
+    }
+
+    /// Check whether a header with provided name exists
+    pub fn contains(&self, name: &str) -> bool {
+        self.entries.contains_key(&name.to_ascii_lowercase())
+    }
+
+    /// Set a header, replacing all values of the same name whatever its casing
+    pub fn insert<K: Into<String>, V: Into<String>>(&mut self, name: K, value: V) {
+        let name = name.into();
+        let key = name.to_ascii_lowercase();
+        match self.entries.get_mut(&key) {
+            Some(e) => e.values = vec![value.into()],
+            None => {
+                self.entries.insert(key, Entry { name, values: vec![value.into()] });
+            }
+        }
+    }
+
+    /// Add a value to a header, keeping its existing values
+    pub fn append<K: Into<String>, V: Into<String>>(&mut self, name: K, value: V) {
+        let name = name.into();
+        self.entries.entry(name.to_ascii_lowercase())
+            .or_insert_with(|| Entry { name, values: Vec::new() })
+            .values
+            .push(value.into());
+    }
+
+    /// Remove a header, returning its values
+    pub fn remove(&mut self, name: &str) -> Vec<String> {
+        self.entries.remove(&name.to_ascii_lowercase())
+            .map(|e| e.values)
+            .unwrap_or_default()
+    }
+
+    /// Iterate over all headers as `(name, value)`, one item per value
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries.values()
+            .flat_map(|e| e.values.iter().map(move |v| (e.name.as_str(), v.as_str())))
+    }
+
+    /// Iterate over all header names
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries.values().map(|e| e.name.as_str())
+    }
+
+    /// Get the number of header names
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check whether there is no header
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Get the `Content-Type` header
+    pub fn content_type(&self) -> Option<&str> {
+        self.get("content-type")
+    }
+
+    /// Get the media type of the `Content-Type` header, lowercased and without parameters
+    ///
+    /// example: `application/json`
+    pub fn mime_type(&self) -> Option<String> {
+        self.content_type()
+            .map(|t| t.split(';').next().unwrap_or_default().trim().to_ascii_lowercase())
+    }
+
+    /// Get the `Content-Length` header
+    pub fn content_length(&self) -> Option<u64> {
+        self.get("content-length")?.trim().parse().ok()
+    }
+
+    /// Get the `Content-Encoding` header
+    pub fn content_encoding(&self) -> Option<&str> {
+        self.get("content-encoding")
+    }
+
+    /// Get the elements of the `Accept` header
+    pub fn accept(&self) -> Vec<&str> {
+        self.get_list("accept")
+    }
+
+    /// Get the elements of the `Accept-Encoding` header
+    pub fn accept_encoding(&self) -> Vec<&str> {
+        self.get_list("accept-encoding")
+    }
+
+    /// Get the `Authorization` header
+    pub fn authorization(&self) -> Option<&str> {
+        self.get("authorization")
+    }
+
+    /// Get the token of a `Authorization: Bearer <token>` header
+    pub fn bearer_token(&self) -> Option<&str> {
+        let auth = self.authorization()?.trim();
+        let (scheme, token) = auth.split_once(' ')?;
+        if !scheme.eq_ignore_ascii_case("bearer") {
+            return None;
+        }
+        Some(token.trim())
+    }
+
+    /// Get the `Host` header
+    pub fn host(&self) -> Option<&str> {
+        self.get("host")
+    }
+
+    /// Get the `Origin` header
+    pub fn origin(&self) -> Option<&str> {
+        self.get("origin")
+    }
+
+    /// Get the `Referer` header
+    pub fn referer(&self) -> Option<&str> {
+        self.get("referer")
+    }
+
+    /// Get the `User-Agent` header
+    pub fn user_agent(&self) -> Option<&str> {
+        self.get("user-agent")
+    }
+}
+
+/// Add names to the `Vary` header, keeping the ones already there
+pub(crate) fn add_vary(headers: &mut HeaderMap, names: &[&str]) {
+    let mut vary: Vec<String> = headers.get_list("vary").into_iter().map(|v| v.to_string()).collect();
+    if vary.iter().any(|v| v == "*") {
+        return;
+    }
+    for name in names {
+        if !vary.iter().any(|v| v.eq_ignore_ascii_case(name)) {
+            vary.push(name.to_string());
+        }
+    }
+    headers.insert("Vary", vary.join(", "));
+}
+
+/// Split a comma-separated header list, ignoring commas in quoted strings
+fn split_list(value: &str) -> Vec<&str> {
+    let mut items = Vec::new();
+    let mut start = 0;
+    let mut quoted = false;
+    let mut escaped = false;
+    for (i, c) in value.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            ',' if !quoted => {
+                items.push(&value[start..i]);
+                start = i + 1;
+            }
+            _ => (),
+        }
+    }
+    items.push(&value[start..]);
+    items.into_iter().map(|i| i.trim()).filter(|i| !i.is_empty()).collect()
+}
+
+impl<K: Into<String>, V: Into<String>> FromIterator<(K, V)> for HeaderMap {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut headers = HeaderMap::new();
+        headers.extend(iter);
+        headers
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> Extend<(K, V)> for HeaderMap {
+    /// Append all headers
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (k, v) in iter {
+            self.append(k, v);
+        }
+    }
+}
+
+impl From<HashMap<String, String>> for HeaderMap {
+    fn from(m: HashMap<String, String>) -> Self {
+        m.into_iter().collect()
+    }
+}
+
+impl From<HeaderMap> for HashMap<String, String> {
+    /// Values of a name are joined with `, `
+    fn from(h: HeaderMap) -> Self {
+        h.entries.into_values()
+            .map(|e| (e.name, e.values.join(", ")))
+            .collect()
+    }
+}
+
+impl Serialize for HeaderMap {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.entries.len()))?;
+        for e in self.entries.values() {
+            map.serialize_entry(&e.name, &e.values.join(", "))?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for HeaderMap {
+    /// `null` is accepted as no header
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let m = Option::<HashMap<String, String>>::deserialize(deserializer)?;
+        Ok(m.map(HeaderMap::from).unwrap_or_default())
+    }
+}
@@ -0,0 +1,67 @@
+use std::fmt;
+use serde::de::DeserializeOwned;
+use crate::common;
+use super::header::HeaderMap;
+
+/// Enum type of errors that may occur during JSON request body extraction
+#[derive(Debug)]
+pub enum JsonError {
+    /// The request has no body
+    MissingBody,
+    /// The `Content-Type` header is missing (`None`) or not a JSON media type
+    UnsupportedContentType(Option<String>),
+    Base64DecodeError(base64::DecodeError),
+    /// The body is not valid JSON or does not match the target type
+    DeserializeError(serde_path_to_error::Error<serde_json::Error>),
+}
+
+impl JsonError {
+    /// Get the path of the field that failed to deserialize
+    ///
+    /// example: `items[0].price`
+    pub fn path(&self) -> Option<String> {
+        match self {
+            JsonError::DeserializeError(e) => Some(e.path().to_string()),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonError::MissingBody => write!(f, "request body is missing"),
+            JsonError::UnsupportedContentType(None) => write!(f, "Content-Type header is missing, expected application/json"),
+            JsonError::UnsupportedContentType(Some(t)) => write!(f, "unsupported Content-Type {}, expected application/json", t),
+            JsonError::Base64DecodeError(e) => write!(f, "invalid base64 body: {}", e),
+            JsonError::DeserializeError(e) => write!(f, "invalid JSON body at {}: {}", e.path(), e.inner()),
+        }
+    }
+}
+
+impl std::error::Error for JsonError {}
+
+/// Check whether a `Content-Type` is `application/json` or `application/*+json`
+pub(crate) fn is_json(content_type: &str) -> bool {
+    let mime = content_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+    match mime.strip_prefix("application/") {
+        Some(sub) => sub == "json" || sub.ends_with("+json"),
+        None => false,
+    }
+}
+
+pub(crate) fn from_body<T: DeserializeOwned>(
+    headers: &HeaderMap,
+    body: &Option<String>,
+    is_base64encoded: bool,
+) -> Result<T, JsonError> {
+    match headers.content_type() {
+        Some(t) if is_json(t) => (),
+        t => return Err(JsonError::UnsupportedContentType(t.map(|t| t.to_string()))),
+    }
+    let body = common::decode_body_binary(body, is_base64encoded)
+        .map_err(JsonError::Base64DecodeError)?
+        .ok_or(JsonError::MissingBody)?;
+    let de = &mut serde_json::Deserializer::from_slice(&body);
+    serde_path_to_error::deserialize(de).map_err(JsonError::DeserializeError)
+}
@@ -1,9 +1,42 @@
 pub mod req;
 pub mod resp;
 pub mod utils;
+pub mod cookie;
+pub mod header;
+#[cfg(feature = "form")]
+pub mod urlencoded;
+#[cfg(feature = "form")]
+pub mod form;
+pub mod multipart;
+pub mod extract;
+pub mod range;
+pub mod conditional;
+#[cfg(feature = "http-types")]
+pub mod convert;
+#[cfg(feature = "tower")]
+pub mod service;
+#[cfg(feature = "json")]
+pub mod json;
+#[cfg(feature = "compression")]
+pub mod compress;
+pub mod v1;
+mod any;
 
-pub use resp::Response;
+pub use resp::{Response, IntoResponse};
+pub use utils::status_code::{StatusCode, InvalidStatusCode};
 pub use req::{Request, RequestSimple};
+pub use any::AnyRequest;
+pub use cookie::{SetCookie, SameSite, CookieJar};
+pub use header::HeaderMap;
+#[cfg(feature = "form")]
+pub use urlencoded::{Pairs, QueryError};
+#[cfg(feature = "form")]
+pub use form::FormError;
+pub use multipart::{Part, MultipartError};
+#[cfg(feature = "http-types")]
+pub use convert::ConvertError;
+#[cfg(feature = "json")]
+pub use json::JsonError;
 
 use lambda_runtime::LambdaEvent;
 
@@ -11,7 +44,7 @@ use lambda_runtime::LambdaEvent;
 /// Used for building API Gateway Lambda proxy integrations for HTTP APIs
 ///
 /// example:
-/// ```rust
+/// ```rust,no_run
 /// use lambda_runtime::{service_fn, Error};
 /// use hegel::http;
 ///
@@ -24,8 +57,12 @@ use lambda_runtime::LambdaEvent;
 ///
 /// async fn func(req: http::Event) -> Result<http::Response, Error> {
 ///     // ...
-///     Ok(http::Response::new_status(200))
+///     Ok(http::Response::new_status(http::StatusCode::OK))
 /// }
 /// ```
 ///
-pub type Event = LambdaEvent<Request>;
\ No newline at end of file
+pub type Event = LambdaEvent<Request>;
+
+/// Event for lambda_runtime service function accepting both payload format 1.0 and 2.0
+/// Used for building API Gateway Lambda proxy integrations for HTTP APIs
+pub type AnyEvent = LambdaEvent<AnyRequest>;
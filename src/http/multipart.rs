@@ -0,0 +1,289 @@
+use std::fmt;
+use crate::common;
+use super::header::HeaderMap;
+
+/// Size limits applied while parsing `multipart/form-data` bodies
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    /// maximum size of the data of a single part, in bytes
+    pub max_part_size: usize,
+    /// maximum size of the data of all parts together, in bytes
+    pub max_total_size: usize,
+    /// maximum number of parts
+    pub max_parts: usize,
+}
+
+impl Default for Limits {
+    /// 6 MB (the Lambda payload limit) for a part and in total, up to 100 parts
+    fn default() -> Self {
+        Limits {
+            max_part_size: 6 * 1024 * 1024,
+            max_total_size: 6 * 1024 * 1024,
+            max_parts: 100,
+        }
+    }
+}
+
+/// A part of a `multipart/form-data` body
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct Part {
+    /// `name` parameter of the `Content-Disposition` header
+    pub name: Option<String>,
+    /// `filename` (or `filename*`) parameter of the `Content-Disposition` header
+    pub filename: Option<String>,
+    /// `Content-Type` header of the part
+    pub content_type: Option<String>,
+    /// all headers of the part, in order
+    pub headers: Vec<(String, String)>,
+    pub data: Vec<u8>,
+}
+
+impl Part {
+    /// Check whether the part is a file upload
+    pub fn is_file(&self) -> bool {
+        self.filename.is_some()
+    }
+
+    /// Get the data of the part as UTF-8 text
+    pub fn text(&self) -> Result<&str, std::str::Utf8Error> {
+        std::str::from_utf8(&self.data)
+    }
+}
+
+/// Enum type of errors that may occur during `multipart/form-data` body parsing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MultipartError {
+    /// The request has no body
+    MissingBody,
+    /// The `Content-Type` header is missing (`None`) or not `multipart/form-data`
+    UnsupportedContentType(Option<String>),
+    /// The `Content-Type` header has no valid `boundary` parameter
+    MissingBoundary,
+    Base64DecodeError(base64::DecodeError),
+    /// The body does not start with, or a part is not terminated by, the boundary
+    MalformedBoundary,
+    /// A part header is not a `name: value` line
+    MalformedHeader,
+    /// A part is larger than `Limits::max_part_size`
+    PartTooLarge(usize),
+    /// All parts together are larger than `Limits::max_total_size`
+    TotalTooLarge(usize),
+    /// There are more parts than `Limits::max_parts`
+    TooManyParts(usize),
+}
+
+impl fmt::Display for MultipartError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MultipartError::MissingBody => write!(f, "request body is missing"),
+            MultipartError::UnsupportedContentType(None) => write!(f, "Content-Type header is missing, expected multipart/form-data"),
+            MultipartError::UnsupportedContentType(Some(t)) => write!(f, "unsupported Content-Type {}, expected multipart/form-data", t),
+            MultipartError::MissingBoundary => write!(f, "Content-Type header has no valid boundary"),
+            MultipartError::Base64DecodeError(e) => write!(f, "invalid base64 body: {}", e),
+            MultipartError::MalformedBoundary => write!(f, "multipart body is not delimited by its boundary"),
+            MultipartError::MalformedHeader => write!(f, "multipart body has a malformed part header"),
+            MultipartError::PartTooLarge(l) => write!(f, "multipart part is larger than {} bytes", l),
+            MultipartError::TotalTooLarge(l) => write!(f, "multipart body is larger than {} bytes", l),
+            MultipartError::TooManyParts(l) => write!(f, "multipart body has more than {} parts", l),
+        }
+    }
+}
+
+impl std::error::Error for MultipartError {}
+
+/// Get the boundary of a `multipart/form-data` `Content-Type`
+pub fn boundary(content_type: &str) -> Result<String, MultipartError> {
+    let mut params = split_params(content_type).into_iter();
+    let mime = params.next().unwrap_or_default();
+    if !mime.trim().eq_ignore_ascii_case("multipart/form-data") {
+        return Err(MultipartError::UnsupportedContentType(Some(content_type.to_string())));
+    }
+    params.filter_map(|p| parse_param(&p))
+        .find(|(k, _)| k.eq_ignore_ascii_case("boundary"))
+        .map(|(_, v)| v)
+        .filter(|b| !b.is_empty() && b.len() <= 70)
+        .ok_or(MultipartError::MissingBoundary)
+}
+
+/// Parse a `multipart/form-data` body delimited by provided boundary
+///
+/// example:
+/// ```
+/// use hegel::http::multipart::{self, Limits};
+///
+/// let body = b"--XyZ\r\n\
+///     Content-Disposition: form-data; name=\"title\"\r\n\r\n\
+///     Holiday\r\n\
+///     --XyZ\r\n\
+///     Content-Disposition: form-data; name=\"photo\"; filename=\"beach.png\"\r\n\
+///     Content-Type: image/png\r\n\r\n\
+///     \x89PNG\r\n\
+///     --XyZ--\r\n";
+/// let parts = multipart::parse(body, "XyZ", &Limits::default()).unwrap();
+/// assert_eq!(parts[0].name.as_deref(), Some("title"));
+/// assert_eq!(parts[0].text(), Ok("Holiday"));
+/// assert_eq!(parts[1].filename.as_deref(), Some("beach.png"));
+/// assert_eq!(parts[1].content_type.as_deref(), Some("image/png"));
+/// assert_eq!(parts[1].data, b"\x89PNG");
+///
+/// let limits = Limits { max_part_size: 4, ..Limits::default() };
+/// assert!(multipart::parse(body, "XyZ", &limits).is_err());
+/// ```
+pub fn parse(body: &[u8], boundary: &str, limits: &Limits) -> Result<Vec<Part>, MultipartError> {
+    let delimiter = format!("--{}", boundary).into_bytes();
+    let close = [b"\r\n".as_slice(), &delimiter].concat();
+
+    // the first delimiter either starts the body or follows the preamble
+    let mut pos = if body.starts_with(&delimiter) {
+        delimiter.len()
+    } else {
+        find(body, &close, 0).ok_or(MultipartError::MalformedBoundary)? + close.len()
+    };
+
+    let mut parts = Vec::new();
+    let mut total = 0;
+    loop {
+        if body[pos..].starts_with(b"--") {
+            return Ok(parts);
+        }
+        while matches!(body.get(pos), Some(b' ') | Some(b'\t')) {
+            pos += 1;
+        }
+        if !body[pos..].starts_with(b"\r\n") {
+            return Err(MultipartError::MalformedBoundary);
+        }
+        pos += 2;
+
+        if parts.len() == limits.max_parts {
+            return Err(MultipartError::TooManyParts(limits.max_parts));
+        }
+        let (headers, start) = if body[pos..].starts_with(b"\r\n") {
+            (Vec::new(), pos + 2)
+        } else {
+            let end = find(body, b"\r\n\r\n", pos).ok_or(MultipartError::MalformedHeader)?;
+            (parse_headers(&body[pos..end])?, end + 4)
+        };
+        let end = find(body, &close, start).ok_or(MultipartError::MalformedBoundary)?;
+        let data = &body[start..end];
+        if data.len() > limits.max_part_size {
+            return Err(MultipartError::PartTooLarge(limits.max_part_size));
+        }
+        total += data.len();
+        if total > limits.max_total_size {
+            return Err(MultipartError::TotalTooLarge(limits.max_total_size));
+        }
+        parts.push(new_part(headers, data.to_vec()));
+        pos = end + close.len();
+    }
+}
+
+pub(crate) fn from_body(
+    headers: &HeaderMap,
+    body: &Option<String>,
+    is_base64encoded: bool,
+    limits: &Limits,
+) -> Result<Vec<Part>, MultipartError> {
+    let content_type = headers.content_type()
+        .ok_or(MultipartError::UnsupportedContentType(None))?;
+    let boundary = boundary(content_type)?;
+    let body = common::decode_body_binary(body, is_base64encoded)
+        .map_err(MultipartError::Base64DecodeError)?
+        .ok_or(MultipartError::MissingBody)?;
+    parse(&body, &boundary, limits)
+}
+
+fn new_part(headers: Vec<(String, String)>, data: Vec<u8>) -> Part {
+    let header = |name: &str| headers.iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case(name))
+        .map(|(_, v)| v.clone());
+    let mut part = Part {
+        content_type: header("content-type"),
+        ..Default::default()
+    };
+    if let Some(disposition) = header("content-disposition") {
+        for (k, v) in split_params(&disposition).iter().skip(1).filter_map(|p| parse_param(p)) {
+            match k.to_ascii_lowercase().as_str() {
+                "name" => part.name = Some(v),
+                "filename" if part.filename.is_none() => part.filename = Some(v),
+                "filename*" => part.filename = decode_ext_value(&v).or(part.filename),
+                _ => (),
+            }
+        }
+    }
+    part.headers = headers;
+    part.data = data;
+    part
+}
+
+fn parse_headers(block: &[u8]) -> Result<Vec<(String, String)>, MultipartError> {
+    let block = String::from_utf8_lossy(block);
+    block.split("\r\n")
+        .map(|line| {
+            let (k, v) = line.split_once(':').ok_or(MultipartError::MalformedHeader)?;
+            Ok((k.trim().to_string(), v.trim().to_string()))
+        })
+        .collect()
+}
+
+/// Split a header value on `;` outside of quoted strings
+fn split_params(value: &str) -> Vec<String> {
+    let mut params = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut escaped = false;
+    for c in value.chars() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            ';' if !quoted => {
+                params.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => (),
+        }
+        current.push(c);
+    }
+    params.push(current);
+    params
+}
+
+/// Parse a `key=value` parameter, unquoting the value
+fn parse_param(param: &str) -> Option<(String, String)> {
+    let (k, v) = param.split_once('=')?;
+    let v = v.trim();
+    let v = match v.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+        Some(quoted) => {
+            let mut unescaped = String::with_capacity(quoted.len());
+            let mut chars = quoted.chars();
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' => unescaped.extend(chars.next()),
+                    c => unescaped.push(c),
+                }
+            }
+            unescaped
+        }
+        None => v.to_string(),
+    };
+    Some((k.trim().to_string(), v))
+}
+
+/// Decode a RFC 5987 `charset'language'value` parameter (UTF-8 only)
+fn decode_ext_value(value: &str) -> Option<String> {
+    let mut spl = value.splitn(3, '\'');
+    let charset = spl.next()?;
+    let _language = spl.next()?;
+    let encoded = spl.next()?;
+    if !charset.eq_ignore_ascii_case("utf-8") {
+        return None;
+    }
+    Some(common::percent_decode(encoded))
+}
+
+fn find(haystack: &[u8], needle: &[u8], from: usize) -> Option<usize> {
+    haystack.get(from..)?
+        .windows(needle.len())
+        .position(|w| w == needle)
+        .map(|i| i + from)
+}
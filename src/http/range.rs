@@ -0,0 +1,237 @@
+//! HTTP `Range` requests (RFC 9110 section 14) over file responses
+//!
+//! example:
+//! ```
+//! use hegel::http::{HeaderMap, Response, SetCookie, StatusCode};
+//!
+//! let mut headers = HeaderMap::new();
+//! headers.insert("Range", "bytes=0-4");
+//! let file = Response::new_file_named(b"hello world".to_vec(), "hello.txt".to_string());
+//! let resp = file.clone().range(&headers, "GET");
+//! assert_eq!(resp.status_code, StatusCode::PARTIAL_CONTENT);
+//! assert_eq!(resp.headers.get("content-range"), Some("bytes 0-4/11"));
+//! assert_eq!(resp.body, "hello");
+//!
+//! // Range only applies to GET
+//! assert_eq!(file.clone().range(&headers, "POST"), file);
+//!
+//! // headers and cookies of the file are kept
+//! headers.insert("Range", "bytes=20-");
+//! let resp = file.etag().header("Cache-Control".to_string(), "max-age=60".to_string())
+//!     .cookie(SetCookie::new("seen".to_string(), "1".to_string()))
+//!     .range(&headers, "GET");
+//! assert_eq!(resp.status_code, StatusCode::RANGE_NOT_SATISFIABLE);
+//! assert_eq!(resp.headers.get("content-range"), Some("bytes */11"));
+//! assert_eq!(resp.headers.get("cache-control"), Some("max-age=60"));
+//! assert!(resp.headers.get("etag").is_some());
+//! assert_eq!(resp.cookies, vec!["seen=1".to_string()]);
+//! assert_eq!(resp.body, "");
+//! ```
+
+use std::time::{SystemTime, UNIX_EPOCH};
+use super::{HeaderMap, Response, StatusCode};
+use super::conditional::strong_eq;
+
+/// Most ranges served in one `multipart/byteranges` response,
+/// requests asking for more get the whole body
+const MAX_RANGES: usize = 32;
+
+/// Inclusive byte range, like `bytes=0-499`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteRange {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl ByteRange {
+    /// Get the number of bytes in the range
+    pub fn len(&self) -> u64 {
+        self.end - self.start + 1
+    }
+
+    /// Always false, a range holds at least one byte
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+}
+
+/// Result of parsing a `Range` header against a body length
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Ranges {
+    /// Ranges to send, sorted and with overlaps merged
+    Satisfiable(Vec<ByteRange>),
+    /// No range overlaps the body, answered with `416 Range Not Satisfiable`
+    Unsatisfiable,
+}
+
+/// Parse a `Range` header against a body of `len` bytes
+///
+/// Returns `None` when the header is malformed or not in `bytes`,
+/// the whole body should then be sent.
+///
+/// example:
+/// ```
+/// use hegel::http::range::{self, ByteRange, Ranges};
+///
+/// assert_eq!(
+///     range::parse("bytes=0-1, -2", 10),
+///     Some(Ranges::Satisfiable(vec![ByteRange { start: 0, end: 1 }, ByteRange { start: 8, end: 9 }]))
+/// );
+/// assert_eq!(range::parse("bytes=5-", 10), Some(Ranges::Satisfiable(vec![ByteRange { start: 5, end: 9 }])));
+/// assert_eq!(range::parse("bytes=20-30", 10), Some(Ranges::Unsatisfiable));
+/// assert_eq!(range::parse("bytes=5-1", 10), None);
+/// assert_eq!(range::parse("items=0-1", 10), None);
+/// ```
+pub fn parse(header: &str, len: u64) -> Option<Ranges> {
+    let (unit, specs) = header.split_once('=')?;
+    if !unit.trim().eq_ignore_ascii_case("bytes") {
+        return None;
+    }
+    let mut ranges = Vec::new();
+    for spec in specs.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        let (first, last) = spec.split_once('-')?;
+        let (first, last) = (first.trim(), last.trim());
+        let range = if first.is_empty() {
+            let suffix: u64 = parse_number(last)?;
+            match suffix {
+                0 => None,
+                _ if len == 0 => None,
+                _ => Some(ByteRange { start: len.saturating_sub(suffix), end: len - 1 }),
+            }
+        } else {
+            let start: u64 = parse_number(first)?;
+            let end = match last {
+                "" => None,
+                last => Some(parse_number(last)?),
+            };
+            if end.map(|end| end < start).unwrap_or(false) {
+                return None;
+            }
+            match start < len {
+                true => Some(ByteRange { start, end: end.unwrap_or(len - 1).min(len - 1) }),
+                false => None,
+            }
+        };
+        ranges.extend(range);
+    }
+    if ranges.is_empty() {
+        return match specs.trim().is_empty() {
+            true => None,
+            false => Some(Ranges::Unsatisfiable),
+        };
+    }
+    ranges.sort_by_key(|r| r.start);
+    let mut merged: Vec<ByteRange> = Vec::with_capacity(ranges.len());
+    for r in ranges {
+        match merged.last_mut() {
+            Some(last) if r.start <= last.end.saturating_add(1) => last.end = last.end.max(r.end),
+            _ => merged.push(r),
+        }
+    }
+    Some(Ranges::Satisfiable(merged))
+}
+
+fn parse_number(s: &str) -> Option<u64> {
+    match !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()) {
+        true => s.parse().ok(),
+        false => None,
+    }
+}
+
+/// Check an `If-Range` validator against the response `ETag` / `Last-Modified`
+///
+/// Entity tags must match strongly, dates must be equal to the `Last-Modified` date.
+fn if_range_matches(if_range: &str, resp: &HeaderMap) -> bool {
+    let if_range = if_range.trim();
+    if if_range.starts_with('"') || if_range.starts_with("W/") {
+        return resp.get("etag").map(|etag| strong_eq(if_range, etag)).unwrap_or(false);
+    }
+    match (httpdate::parse_http_date(if_range), resp.get("last-modified").map(httpdate::parse_http_date)) {
+        (Ok(date), Some(Ok(modified))) => date == modified,
+        _ => false,
+    }
+}
+
+pub(crate) fn apply(mut resp: Response, req: &HeaderMap, method: &str) -> Response {
+    if resp.status_code != StatusCode::OK || !method.eq_ignore_ascii_case("GET") {
+        return resp;
+    }
+    resp.headers.insert("Accept-Ranges", "bytes");
+    let header = match req.get("range") {
+        Some(h) => h,
+        None => return resp,
+    };
+    if let Some(if_range) = req.get("if-range") {
+        if !if_range_matches(if_range, &resp.headers) {
+            return resp;
+        }
+    }
+    let body = match resp.is_base64encoded {
+        true => match base64::decode(&resp.body) {
+            Ok(b) => b,
+            Err(_) => return resp,
+        },
+        false => resp.body.as_bytes().to_vec(),
+    };
+    let len = body.len() as u64;
+    let ranges = match parse(header, len) {
+        Some(Ranges::Satisfiable(ranges)) if ranges.len() <= MAX_RANGES => ranges,
+        Some(Ranges::Unsatisfiable) => {
+            resp.status_code = StatusCode::RANGE_NOT_SATISFIABLE;
+            resp.body = String::new();
+            resp.is_base64encoded = false;
+            resp.headers.insert("Content-Range", format!("bytes */{}", len));
+            resp.headers.insert("Content-Length", "0");
+            return resp;
+        }
+        _ => return resp,
+    };
+
+    let content_type = resp.headers.content_type().unwrap_or("application/octet-stream").to_string();
+    let text = !resp.is_base64encoded;
+    let (body, content_type) = match ranges.as_slice() {
+        [r] => {
+            resp.headers.insert("Content-Range", format!("bytes {}-{}/{}", r.start, r.end, len));
+            (body[r.start as usize..=r.end as usize].to_vec(), content_type)
+        }
+        _ => {
+            resp.headers.remove("content-range");
+            let boundary = boundary(len);
+            let mut out = Vec::new();
+            for r in &ranges {
+                out.extend_from_slice(format!(
+                    "\r\n--{}\r\nContent-Type: {}\r\nContent-Range: bytes {}-{}/{}\r\n\r\n",
+                    boundary, content_type, r.start, r.end, len
+                ).as_bytes());
+                out.extend_from_slice(&body[r.start as usize..=r.end as usize]);
+            }
+            out.extend_from_slice(format!("\r\n--{}--\r\n", boundary).as_bytes());
+            (out, format!("multipart/byteranges; boundary={}", boundary))
+        }
+    };
+
+    resp.status_code = StatusCode::PARTIAL_CONTENT;
+    resp.headers.insert("Content-Type", content_type);
+    resp.headers.insert("Content-Length", body.len().to_string());
+    match String::from_utf8(body) {
+        Ok(body) if text => {
+            resp.body = body;
+            resp.is_base64encoded = false;
+        }
+        Ok(body) => {
+            resp.body = base64::encode(body);
+            resp.is_base64encoded = true;
+        }
+        Err(e) => {
+            resp.body = base64::encode(e.into_bytes());
+            resp.is_base64encoded = true;
+        }
+    }
+    resp
+}
+
+/// Generate a `multipart/byteranges` boundary
+fn boundary(len: u64) -> String {
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_nanos()).unwrap_or_default();
+    format!("hegel-byteranges-{:x}{:x}", nanos, len)
+}
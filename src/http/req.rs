@@ -1,11 +1,25 @@
 use crate::common;
+use super::cookie::CookieJar;
+use super::header::HeaderMap;
 use std::collections::HashMap;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use serde::{Serialize, Deserialize};
+use super::multipart::{Part, MultipartError, Limits};
+
+#[cfg(any(feature = "form", feature = "json"))]
+use serde::de::DeserializeOwned;
+
+#[cfg(feature = "form")]
+use super::form::FormError;
+#[cfg(feature = "form")]
+use super::urlencoded::{Pairs, QueryError};
 
 #[cfg(feature = "chrono")]
 use chrono::{DateTime, TimeZone, Utc};
 
+#[cfg(feature = "json")]
+use super::json::JsonError;
+
 /// **lambda_runtime** service simplified payload type
 /// Used for building API Gateway Lambda proxy integrations for HTTP APIs
 ///
@@ -16,7 +30,7 @@ use chrono::{DateTime, TimeZone, Utc};
 ///
 /// async fn handler(req: LambdaEvent<http::req::RequestSimple>) -> Result<http::Response, Error> {
 ///     //...
-///     Ok(http::Response::new_status(200))
+///     Ok(http::Response::new_status(http::StatusCode::OK))
 /// }
 /// ```
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -24,7 +38,8 @@ use chrono::{DateTime, TimeZone, Utc};
 pub struct RequestSimple {
     pub raw_path: String,
     pub cookies: Option<Vec<String>>,
-    pub headers: HashMap<String, String>,
+    #[serde(default)]
+    pub headers: HeaderMap,
     pub query_string_parameters: Option<HashMap<String,String>>,
     pub request_context: common::RequestContext,
     pub body: Option<String>,
@@ -44,7 +59,7 @@ pub struct RequestSimple {
 ///
 /// async fn handler(req: LambdaEvent<http::req::Request>) -> Result<http::Response, Error> {
 ///     //...
-///     Ok(http::Response::new_status(200))
+///     Ok(http::Response::new_status(http::StatusCode::OK))
 /// }
 /// ```
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -55,7 +70,8 @@ pub struct Request {
     pub raw_path: String,
     pub raw_query_string: String,
     pub cookies: Option<Vec<String>>,
-    pub headers: HashMap<String, String>,
+    #[serde(default)]
+    pub headers: HeaderMap,
     pub query_string_parameters: Option<HashMap<String,String>>,
     pub request_context: common::RequestContext,
     pub body: Option<String>,
@@ -65,40 +81,114 @@ pub struct Request {
     pub stage_variables: Option<HashMap<String, String>>,
 }
 
-/// Enum type of errors that may occur during request body parsing
-#[derive(Debug, Clone)]
-pub enum ParseBodyError {
-    Base64DecodeError(base64::DecodeError),
-    FromUtf8Error(std::string::FromUtf8Error)
-}
+pub use common::ParseBodyError;
 
 impl RequestSimple {
     /// Get user request body as String (UTF-8)
     pub fn body(&self) -> Result<Option<String>, ParseBodyError> {
-        if self.body.is_none() {
-            return Ok(None)
-        }
-        return if !self.is_base64encoded {
-            Ok(self.body.clone())
-        } else {
-            let result = base64::decode(self.body.clone().unwrap());
-            if result.is_err() { return Err(ParseBodyError::Base64DecodeError(result.unwrap_err()))}
-            let result = String::from_utf8(result.unwrap());
-            if result.is_err() {return Err(ParseBodyError::FromUtf8Error(result.unwrap_err()))}
-            Ok(Some(result.unwrap()))
-        }
+        common::decode_body(&self.body, self.is_base64encoded)
     }
 
     /// Get user request body as binary
     pub fn body_binary(&self) -> Result<Option<Vec<u8>>, base64::DecodeError> {
-        if self.body.is_none() {
-            return Ok(None)
-        }
-        return if !self.is_base64encoded {
-            Ok(self.body.clone().map(|b|b.as_bytes().to_vec()))
-        } else {
-            Ok(Some(base64::decode(self.body.clone().unwrap())?.as_slice().to_vec()))
-        }
+        common::decode_body_binary(&self.body, self.is_base64encoded)
+    }
+
+    /// Get user request body as ordered urlencoded form pairs
+    ///
+    /// The `Content-Type` must be `application/x-www-form-urlencoded`
+    /// ! Remember to enable feature **form** before using it !
+    #[cfg(feature = "form")]
+    pub fn form_pairs(&self) -> Result<Pairs, FormError> {
+        super::form::pairs_from_body(&self.headers, &self.body, self.is_base64encoded)
+    }
+
+    /// Get user request body deserialized from an urlencoded form
+    ///
+    /// The `Content-Type` must be `application/x-www-form-urlencoded`.
+    /// Repeated keys can be collected into `Vec` fields, missing keys into `Option` fields.
+    /// ! Remember to enable feature **form** before using it !
+    ///
+    /// example:
+    /// ```
+    /// use hegel::http;
+    /// use lambda_runtime::Error;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Login {
+    ///     username: String,
+    ///     password: String,
+    ///     remember: Option<String>,
+    /// }
+    ///
+    /// async fn handler(req: http::Event) -> Result<http::Response, Error> {
+    ///     let login: Login = match req.payload.form() {
+    ///         Ok(login) => login,
+    ///         Err(e) => return Ok(http::Response::new_status(http::StatusCode::BAD_REQUEST).body_text(e.to_string())),
+    ///     };
+    ///     Ok(http::Response::new_text(login.username))
+    /// }
+    /// ```
+    #[cfg(feature = "form")]
+    pub fn form<T: DeserializeOwned>(&self) -> Result<T, FormError> {
+        super::form::from_body(&self.headers, &self.body, self.is_base64encoded)
+    }
+
+    /// Get the parts of a `multipart/form-data` user request body
+    /// with default **multipart::Limits**
+    pub fn multipart(&self) -> Result<Vec<Part>, MultipartError> {
+        self.multipart_with_limits(&Limits::default())
+    }
+
+    /// Get the parts of a `multipart/form-data` user request body
+    ///
+    /// example:
+    /// ```
+    /// use hegel::http::{self, multipart::Limits};
+    /// use lambda_runtime::Error;
+    ///
+    /// async fn handler(req: http::Event) -> Result<http::Response, Error> {
+    ///     let limits = Limits { max_part_size: 1024 * 1024, ..Limits::default() };
+    ///     let parts = match req.payload.multipart_with_limits(&limits) {
+    ///         Ok(parts) => parts,
+    ///         Err(e) => return Ok(http::Response::new_status(http::StatusCode::BAD_REQUEST).body_text(e.to_string())),
+    ///     };
+    ///     let files = parts.iter().filter(|p| p.is_file()).count();
+    ///     Ok(http::Response::new_text(format!("{} files uploaded", files)))
+    /// }
+    /// ```
+    pub fn multipart_with_limits(&self, limits: &Limits) -> Result<Vec<Part>, MultipartError> {
+        super::multipart::from_body(&self.headers, &self.body, self.is_base64encoded, limits)
+    }
+
+    /// Get user request body deserialized from JSON
+    ///
+    /// The `Content-Type` must be `application/json` or `application/*+json`
+    /// ! Remember to enable feature **json** before using it !
+    ///
+    /// example:
+    /// ```
+    /// use hegel::http;
+    /// use lambda_runtime::Error;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Item {
+    ///     name: String,
+    /// }
+    ///
+    /// async fn handler(req: http::Event) -> Result<http::Response, Error> {
+    ///     let item: Item = match req.payload.json() {
+    ///         Ok(item) => item,
+    ///         Err(e) => return Ok(http::Response::new_status(http::StatusCode::BAD_REQUEST).body_text(e.to_string())),
+    ///     };
+    ///     Ok(http::Response::new_text(item.name))
+    /// }
+    /// ```
+    #[cfg(feature = "json")]
+    pub fn json<T: DeserializeOwned>(&self) -> Result<T, JsonError> {
+        super::json::from_body(&self.headers, &self.body, self.is_base64encoded)
     }
 
     /// Get HTTP request path
@@ -109,25 +199,12 @@ impl RequestSimple {
     }
 
     /// Get user request cookies
-    pub fn cookies(&self) -> Option<HashMap<String, String>> {
-        let cs = self.cookies.clone();
-        if cs.is_none() {
-            return None
-        }
-        let cs = cs.unwrap();
-        let mut result = HashMap::new();
-        for c in cs {
-            let spl = c.split("=").collect::<Vec<&str>>();
-            if spl.len() != 2 {
-                continue;
-            }
-            result.insert(spl[0].to_string(), spl[1].to_string());
-        }
-        Some(result)
+    pub fn cookies(&self) -> CookieJar {
+        CookieJar::parse(self.cookies.iter().flatten().map(|c| c.as_str()))
     }
 
     /// Get user request headers
-    pub fn headers(&self) -> HashMap<String, String> {
+    pub fn headers(&self) -> HeaderMap {
         self.headers.clone()
     }
 
@@ -199,36 +276,138 @@ impl RequestSimple {
     pub fn protocol(&self) -> String {
         self.request_context.http.protocol.clone()
     }
+
+    /// Get the IAM caller identity
+    /// (requests authorized by AWS IAM, like Lambda function URLs with `AWS_IAM` auth type)
+    pub fn iam(&self) -> Option<&common::IamAuthorizer> {
+        self.request_context.authorizer.as_ref()?.iam.as_ref()
+    }
+
+    /// Get the ARN of the IAM caller
+    ///
+    /// example: `arn:aws:iam::111122223333:user/example-user`
+    pub fn user_arn(&self) -> Option<String> {
+        self.iam()?.user_arn.clone()
+    }
+
+    /// Get the access key of the IAM caller
+    pub fn access_key(&self) -> Option<String> {
+        self.iam()?.access_key.clone()
+    }
+
+    /// Get the AWS account ID of the IAM caller
+    pub fn caller_account_id(&self) -> Option<String> {
+        self.iam()?.account_id.clone()
+    }
 }
 
 
 impl Request {
     /// Get user request body as String (UTF-8)
     pub fn body(&self) -> Result<Option<String>, ParseBodyError> {
-        if self.body.is_none() {
-            return Ok(None)
-        }
-        return if !self.is_base64encoded {
-            Ok(self.body.clone())
-        } else {
-            let result = base64::decode(self.body.clone().unwrap());
-            if result.is_err() { return Err(ParseBodyError::Base64DecodeError(result.unwrap_err()))}
-            let result = String::from_utf8(result.unwrap());
-            if result.is_err() {return Err(ParseBodyError::FromUtf8Error(result.unwrap_err()))}
-            Ok(Some(result.unwrap()))
-        }
+        common::decode_body(&self.body, self.is_base64encoded)
     }
 
     /// Get user request body as binary
     pub fn body_binary(&self) -> Result<Option<Vec<u8>>, base64::DecodeError> {
-        if self.body.is_none() {
-            return Ok(None)
-        }
-        return if !self.is_base64encoded {
-            Ok(self.body.clone().map(|b|b.as_bytes().to_vec()))
-        } else {
-            Ok(Some(base64::decode(self.body.clone().unwrap())?.as_slice().to_vec()))
-        }
+        common::decode_body_binary(&self.body, self.is_base64encoded)
+    }
+
+    /// Get user request body as ordered urlencoded form pairs
+    ///
+    /// The `Content-Type` must be `application/x-www-form-urlencoded`
+    /// ! Remember to enable feature **form** before using it !
+    #[cfg(feature = "form")]
+    pub fn form_pairs(&self) -> Result<Pairs, FormError> {
+        super::form::pairs_from_body(&self.headers, &self.body, self.is_base64encoded)
+    }
+
+    /// Get user request body deserialized from an urlencoded form
+    ///
+    /// The `Content-Type` must be `application/x-www-form-urlencoded`.
+    /// Repeated keys can be collected into `Vec` fields, missing keys into `Option` fields.
+    /// ! Remember to enable feature **form** before using it !
+    ///
+    /// example:
+    /// ```
+    /// use hegel::http;
+    /// use lambda_runtime::Error;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Login {
+    ///     username: String,
+    ///     password: String,
+    ///     remember: Option<String>,
+    /// }
+    ///
+    /// async fn handler(req: http::Event) -> Result<http::Response, Error> {
+    ///     let login: Login = match req.payload.form() {
+    ///         Ok(login) => login,
+    ///         Err(e) => return Ok(http::Response::new_status(http::StatusCode::BAD_REQUEST).body_text(e.to_string())),
+    ///     };
+    ///     Ok(http::Response::new_text(login.username))
+    /// }
+    /// ```
+    #[cfg(feature = "form")]
+    pub fn form<T: DeserializeOwned>(&self) -> Result<T, FormError> {
+        super::form::from_body(&self.headers, &self.body, self.is_base64encoded)
+    }
+
+    /// Get the parts of a `multipart/form-data` user request body
+    /// with default **multipart::Limits**
+    pub fn multipart(&self) -> Result<Vec<Part>, MultipartError> {
+        self.multipart_with_limits(&Limits::default())
+    }
+
+    /// Get the parts of a `multipart/form-data` user request body
+    ///
+    /// example:
+    /// ```
+    /// use hegel::http::{self, multipart::Limits};
+    /// use lambda_runtime::Error;
+    ///
+    /// async fn handler(req: http::Event) -> Result<http::Response, Error> {
+    ///     let limits = Limits { max_part_size: 1024 * 1024, ..Limits::default() };
+    ///     let parts = match req.payload.multipart_with_limits(&limits) {
+    ///         Ok(parts) => parts,
+    ///         Err(e) => return Ok(http::Response::new_status(http::StatusCode::BAD_REQUEST).body_text(e.to_string())),
+    ///     };
+    ///     let files = parts.iter().filter(|p| p.is_file()).count();
+    ///     Ok(http::Response::new_text(format!("{} files uploaded", files)))
+    /// }
+    /// ```
+    pub fn multipart_with_limits(&self, limits: &Limits) -> Result<Vec<Part>, MultipartError> {
+        super::multipart::from_body(&self.headers, &self.body, self.is_base64encoded, limits)
+    }
+
+    /// Get user request body deserialized from JSON
+    ///
+    /// The `Content-Type` must be `application/json` or `application/*+json`
+    /// ! Remember to enable feature **json** before using it !
+    ///
+    /// example:
+    /// ```
+    /// use hegel::http;
+    /// use lambda_runtime::Error;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Item {
+    ///     name: String,
+    /// }
+    ///
+    /// async fn handler(req: http::Event) -> Result<http::Response, Error> {
+    ///     let item: Item = match req.payload.json() {
+    ///         Ok(item) => item,
+    ///         Err(e) => return Ok(http::Response::new_status(http::StatusCode::BAD_REQUEST).body_text(e.to_string())),
+    ///     };
+    ///     Ok(http::Response::new_text(item.name))
+    /// }
+    /// ```
+    #[cfg(feature = "json")]
+    pub fn json<T: DeserializeOwned>(&self) -> Result<T, JsonError> {
+        super::json::from_body(&self.headers, &self.body, self.is_base64encoded)
     }
 
     /// Get HTTP request path
@@ -239,25 +418,12 @@ impl Request {
     }
 
     /// Get user request cookies
-    pub fn cookies(&self) -> Option<HashMap<String, String>> {
-        let cs = self.cookies.clone();
-        if cs.is_none() {
-            return None
-        }
-        let cs = cs.unwrap();
-        let mut result = HashMap::new();
-        for c in cs {
-            let spl = c.split("=").collect::<Vec<&str>>();
-            if spl.len() != 2 {
-                continue;
-            }
-            result.insert(spl[0].to_string(), spl[1].to_string());
-        }
-        Some(result)
+    pub fn cookies(&self) -> CookieJar {
+        CookieJar::parse(self.cookies.iter().flatten().map(|c| c.as_str()))
     }
 
     /// Get user request headers
-    pub fn headers(&self) -> HashMap<String, String> {
+    pub fn headers(&self) -> HeaderMap {
         self.headers.clone()
     }
 
@@ -274,6 +440,53 @@ impl Request {
         self.query_string_parameters.clone()
     }
 
+    /// Get user request queries parsed from the raw query string
+    ///
+    /// Unlike `queries`, repeated keys are kept apart and in order
+    /// ! Remember to enable feature **form** before using it !
+    ///
+    /// example:
+    /// URL: `https://iochen.com/foor/bar?tag=a,b&tag=c&q=x%20y`
+    /// Result Pairs:
+    /// ```text
+    /// "tag" -> "a,b"
+    /// "tag" -> "c"
+    /// "q" -> "x y"
+    /// ```
+    #[cfg(feature = "form")]
+    pub fn query_pairs(&self) -> Pairs {
+        Pairs::parse(self.raw_query_string.as_bytes())
+    }
+
+    /// Get user request queries deserialized from the raw query string
+    ///
+    /// Repeated keys can be collected into `Vec` fields, missing keys into `Option` fields.
+    /// ! Remember to enable feature **form** before using it !
+    ///
+    /// example:
+    /// ```
+    /// use hegel::http;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Filter {
+    ///     tag: Vec<String>,
+    ///     page: Option<u32>,
+    /// }
+    ///
+    /// let req = http::Request {
+    ///     raw_query_string: "tag=a,b&tag=c".to_string(),
+    ///     ..Default::default()
+    /// };
+    /// let filter: Filter = req.query().unwrap();
+    /// assert_eq!(filter.tag, vec!["a,b", "c"]);
+    /// assert_eq!(filter.page, None);
+    /// ```
+    #[cfg(feature = "form")]
+    pub fn query<T: DeserializeOwned>(&self) -> Result<T, QueryError> {
+        serde_html_form::from_str(&self.raw_query_string).map_err(QueryError)
+    }
+
     /// Get route params
     ///
     /// example:
@@ -329,8 +542,27 @@ impl Request {
     pub fn protocol(&self) -> String {
         self.request_context.http.protocol.clone()
     }
-}
 
+    /// Get the IAM caller identity
+    /// (requests authorized by AWS IAM, like Lambda function URLs with `AWS_IAM` auth type)
+    pub fn iam(&self) -> Option<&common::IamAuthorizer> {
+        self.request_context.authorizer.as_ref()?.iam.as_ref()
+    }
 
+    /// Get the ARN of the IAM caller
+    ///
+    /// example: `arn:aws:iam::111122223333:user/example-user`
+    pub fn user_arn(&self) -> Option<String> {
+        self.iam()?.user_arn.clone()
+    }
 
+    /// Get the access key of the IAM caller
+    pub fn access_key(&self) -> Option<String> {
+        self.iam()?.access_key.clone()
+    }
 
+    /// Get the AWS account ID of the IAM caller
+    pub fn caller_account_id(&self) -> Option<String> {
+        self.iam()?.account_id.clone()
+    }
+}
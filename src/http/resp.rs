@@ -1,6 +1,10 @@
-use std::cmp::min;
-use std::collections::HashMap;
+use std::time::SystemTime;
 use serde::{Serialize, Deserialize};
+use crate::common;
+use super::cookie::SetCookie;
+use super::header::HeaderMap;
+use super::utils::mime;
+use super::utils::status_code::{StatusCode, InvalidStatusCode};
 
 /// **lambda_runtime** service function return payload type
 /// Used for building API Gateway Lambda proxy integrations for HTTP APIs
@@ -12,7 +16,7 @@ use serde::{Serialize, Deserialize};
 ///
 /// async fn handler(req: http::Event) -> Result<http::Response, Error> {
 ///     //...
-///     Ok(http::Response::new_status(200))
+///     Ok(http::Response::new_status(http::StatusCode::OK))
 /// }
 /// ```
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -20,87 +24,199 @@ use serde::{Serialize, Deserialize};
 pub struct Response {
     #[serde(rename = "isBase64Encoded")]
     pub is_base64encoded: bool,
-    pub status_code: u16,
+    pub status_code: StatusCode,
     pub body: String,
-    pub headers: HashMap<String, String>,
+    pub headers: HeaderMap,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cookies: Vec<String>,
 }
 
 impl Response {
     /// return a file as Response
+    ///
+    /// The type is sniffed from the content, UTF-8 text is sent without base64.
+    /// `Content-Type` and `Content-Length` are set.
+    ///
+    /// example:
+    /// ```
+    /// use hegel::http::Response;
+    ///
+    /// let resp = Response::new_file(b"%PDF-1.7 ...".to_vec());
+    /// assert_eq!(resp.headers.content_type(), Some("application/pdf"));
+    /// assert!(resp.is_base64encoded);
+    ///
+    /// let resp = Response::new_file("héllo".as_bytes().to_vec());
+    /// assert_eq!(resp.headers.content_type(), Some("text/plain; charset=utf-8"));
+    /// assert_eq!(resp.headers.content_length(), Some(6));
+    /// assert_eq!(resp.body, "héllo");
+    ///
+    /// let resp = Response::new_file(Vec::new());
+    /// assert_eq!(resp.headers.content_length(), Some(0));
+    /// ```
     pub fn new_file(b: Vec<u8>) -> Response {
-        let mut headers = HashMap::new();
-        let mime = infer::get(b.get(0..min(31, b.len() - 1)).unwrap());
-        if mime.is_some() {
-            headers.insert("Content-Type".to_string(), mime.unwrap().to_string());
-        } else {
-            headers.insert("Content-Type".to_string(), "application/octet-stream".to_string());
-        }
-        Response {
-            is_base64encoded: true,
-            status_code: 200,
-            body: base64::encode(b),
-            headers
-        }
+        Response::default().body_file(b)
+    }
+
+    /// return a named file as Response
+    ///
+    /// Like **new_file**, the type is guessed from the file name extension when sniffing fails.
+    ///
+    /// example:
+    /// ```
+    /// use hegel::http::Response;
+    ///
+    /// let resp = Response::new_file_named(b"body { color: red }".to_vec(), "site.css".to_string());
+    /// assert_eq!(resp.headers.content_type(), Some("text/css; charset=utf-8"));
+    /// assert!(!resp.is_base64encoded);
+    ///
+    /// let resp = Response::new_file_named(vec![0, 159, 146, 150], "data.bin".to_string());
+    /// assert_eq!(resp.headers.content_type(), Some("application/octet-stream"));
+    /// assert!(resp.is_base64encoded);
+    /// ```
+    pub fn new_file_named(b: Vec<u8>, filename: String) -> Response {
+        Response::default().body_file_named(b, filename)
     }
 
     /// return html(UTF-8) as Response
     pub fn new_html(b: String) -> Response {
-        let mut headers = HashMap::new();
+        let mut headers = HeaderMap::new();
         headers.insert("Content-Type".to_string(), "text/html; charset=utf-8".to_string());
         Response {
             is_base64encoded: false,
-            status_code: 200,
+            status_code: StatusCode::OK,
             body: b,
-            headers
+            headers,
+            cookies: Vec::new(),
         }
     }
 
     /// return json as Response
     pub fn new_json(b: String) -> Response {
-        let mut headers = HashMap::new();
+        let mut headers = HeaderMap::new();
         headers.insert("Content-Type".to_string(), "application/json".to_string());
         Response {
             is_base64encoded: false,
-            status_code: 200,
+            status_code: StatusCode::OK,
             body: b,
-            headers
+            headers,
+            cookies: Vec::new(),
         }
     }
 
+    /// return a value serialized to JSON as Response
+    /// ! Remember to enable feature **json** before using it !
+    ///
+    /// example:
+    /// ```
+    /// use std::collections::HashMap;
+    /// use hegel::http;
+    ///
+    /// let resp = http::Response::json(&HashMap::from([("id", 42)])).unwrap();
+    /// assert_eq!(resp.body, r#"{"id":42}"#);
+    /// ```
+    #[cfg(feature = "json")]
+    pub fn json<T: Serialize + ?Sized>(v: &T) -> Result<Response, serde_json::Error> {
+        Ok(Response::new_json(serde_json::to_string(v)?))
+    }
+
     /// return text(UTF-8) as Response
     pub fn new_text(b: String) -> Response {
-        let mut headers = HashMap::new();
+        let mut headers = HeaderMap::new();
         headers.insert("Content-Type".to_string(), "text/plain; charset=utf-8".to_string());
         Response {
             is_base64encoded: false,
-            status_code: 200,
+            status_code: StatusCode::OK,
             body: b,
-            headers
+            headers,
+            cookies: Vec::new(),
         }
     }
 
     /// return a HTTP status as Response
-    pub fn new_status(s: u16) -> Response {
-        let mut headers = HashMap::new();
+    ///
+    /// The body is the status line, like `404 Not Found`
+    pub fn new_status(s: StatusCode) -> Response {
+        let mut headers = HeaderMap::new();
         headers.insert("Content-Type".to_string(), "text/plain; charset=utf-8".to_string());
         Response {
             is_base64encoded: false,
             status_code: s,
-            body: super::utils::status_code::meaning(s).unwrap_or("An unknown error occurred").to_string(),
-            headers
+            body: s.to_string(),
+            headers,
+            cookies: Vec::new(),
         }
     }
 
-    /// return a Response with provided header added
+    /// return a HTTP status number as Response,
+    /// or an error when it is not a valid status code (`100..=599`)
+    ///
+    /// example:
+    /// ```
+    /// use hegel::http::{Response, StatusCode};
+    ///
+    /// assert_eq!(Response::try_new_status(404).unwrap(), Response::new_status(StatusCode::NOT_FOUND));
+    /// assert!(Response::try_new_status(1000).is_err());
+    /// ```
+    pub fn try_new_status(s: u16) -> Result<Response, InvalidStatusCode> {
+        StatusCode::try_from(s).map(Response::new_status)
+    }
+
+    /// return a Response with provided header set,
+    /// replacing the values of the same header whatever its casing
     /// like s struct builder
     pub fn header(mut self, k: String, v: String) -> Response {
-        self.headers.insert(k,v);
+        self.headers.insert(k, v);
+        self
+    }
+
+    /// return a Response with provided header value added,
+    /// keeping the values already set
+    /// like s struct builder
+    pub fn append_header(mut self, k: String, v: String) -> Response {
+        self.headers.append(k, v);
+        self
+    }
+
+    /// return a Response with provided cookie added
+    /// like s struct builder
+    pub fn cookie(mut self, cookie: SetCookie) -> Response {
+        self.cookies.push(cookie.to_string());
         self
     }
 
+    /// return a Response telling the browser to delete the cookie with provided name
+    /// like s struct builder
+    ///
+    /// Cookies of the same name already added to the Response are dropped.
+    /// Use `cookie(SetCookie::new(name, String::new()).path(..).expired())`
+    /// when the cookie was set with a `Domain` or `Path`.
+    ///
+    /// example:
+    /// ```
+    /// use hegel::http::{Response, SetCookie};
+    ///
+    /// let resp = Response::new_text("bye".to_string())
+    ///     .cookie(SetCookie::new("user id".to_string(), "1".to_string()).path("/".to_string()))
+    ///     .cookie(SetCookie::new("theme".to_string(), "dark".to_string()))
+    ///     .remove_cookie("user id".to_string());
+    /// assert_eq!(resp.cookies.len(), 2);
+    /// assert_eq!(resp.cookies[0], "theme=dark");
+    /// assert!(resp.cookies[1].starts_with("user%20id=; Expires="));
+    /// ```
+    pub fn remove_cookie(mut self, name: String) -> Response {
+        let removed = SetCookie::new(name, String::new()).expired();
+        let written = removed.to_string();
+        let written_name = written.split('=').next().unwrap_or_default();
+        self.cookies.retain(|c| {
+            let n = c.split('=').next().unwrap_or_default().trim();
+            n != written_name && n != removed.name
+        });
+        self.cookie(removed)
+    }
+
     /// return a Response with provided status code added
     /// like s struct builder
-    pub fn status_code(mut self, s:u16) -> Response {
+    pub fn status_code(mut self, s: StatusCode) -> Response {
         self.status_code = s;
         self
     }
@@ -134,15 +250,193 @@ impl Response {
 
     /// return a Response with provided file body added
     /// like s struct builder
-    pub fn body_file(mut self, b: Vec<u8>) -> Response {
-        let mime = infer::get(b.get(0..min(31, b.len() - 1)).unwrap());
-        if mime.is_some() {
-            self.headers.insert("Content-Type".to_string(), mime.unwrap().to_string());
+    ///
+    /// See **new_file**
+    pub fn body_file(self, b: Vec<u8>) -> Response {
+        self.file(b, None)
+    }
+
+    /// return a Response with provided named file body added
+    /// like s struct builder
+    ///
+    /// See **new_file_named**
+    pub fn body_file_named(self, b: Vec<u8>, filename: String) -> Response {
+        self.file(b, Some(&filename))
+    }
+
+    fn file(mut self, b: Vec<u8>, filename: Option<&str>) -> Response {
+        let sniffed = infer::get(&b).map(|t| t.mime_type());
+        let mime = sniffed.or_else(|| filename.and_then(mime::from_filename));
+        let text = std::str::from_utf8(&b)
+            .map(|text| mime.map(mime::is_text).unwrap_or_else(|| is_plain_text(text)))
+            .unwrap_or(false);
+        let content_type = match mime {
+            Some(m) if text => format!("{}; charset=utf-8", m),
+            Some(m) => m.to_string(),
+            None if text => "text/plain; charset=utf-8".to_string(),
+            None => "application/octet-stream".to_string(),
+        };
+        self.headers.insert("Content-Type", content_type);
+        self.headers.insert("Content-Length", b.len().to_string());
+        if text {
+            self.body = String::from_utf8(b).unwrap_or_default();
         } else {
-            self.headers.insert("Content-Type".to_string(), "application/octet-stream".to_string());
+            self.body = base64::encode(b);
         }
-        self.body = base64::encode(b);
-        self.is_base64encoded = true;
+        self.is_base64encoded = !text;
+        self
+    }
+
+    /// return the part of the Response asked by the `Range` header of provided request headers
+    ///
+    /// Only applies to `200 OK` responses to `GET` requests, which get `Accept-Ranges: bytes`,
+    /// other responses are returned unchanged.
+    /// One range is answered with `206 Partial Content` and `Content-Range`,
+    /// several with a `multipart/byteranges` body, and ranges outside of the body with
+    /// `416 Range Not Satisfiable`. With `If-Range`, the range is only served when the
+    /// validator matches the `ETag` or `Last-Modified` header of the Response.
+    ///
+    /// example:
+    /// ```
+    /// use hegel::http;
+    /// use lambda_runtime::Error;
+    ///
+    /// async fn handler(req: http::Event) -> Result<http::Response, Error> {
+    ///     let pdf = b"%PDF-1.7 ...".to_vec();
+    ///     Ok(http::Response::new_file(pdf).range(&req.payload.headers, &req.payload.method()))
+    /// }
+    /// ```
+    pub fn range(self, req: &HeaderMap, method: &str) -> Response {
+        super::range::apply(self, req, method)
+    }
+
+    /// return a Response with a strong `ETag` computed from its body added
+    /// like s struct builder
+    ///
+    /// See **http::conditional**
+    pub fn etag(mut self) -> Response {
+        let etag = super::conditional::entity_tag(&self.body_bytes());
+        self.headers.insert("ETag", etag);
+        self
+    }
+
+    /// return a Response with a weak `ETag` computed from its body added
+    /// like s struct builder
+    ///
+    /// Weak tags only work with `If-None-Match`, use them when equivalent bodies
+    /// may not be byte-for-byte identical.
+    pub fn weak_etag(mut self) -> Response {
+        let etag = super::conditional::entity_tag(&self.body_bytes());
+        self.headers.insert("ETag", format!("W/{}", etag));
+        self
+    }
+
+    /// return a Response with provided `Last-Modified` time added
+    /// like s struct builder
+    ///
+    /// HTTP dates only cover the years 1970 to 9999, times outside are clamped to them.
+    ///
+    /// example:
+    /// ```
+    /// use std::time::{Duration, UNIX_EPOCH};
+    /// use hegel::http::Response;
+    ///
+    /// let resp = Response::new_text("v1".to_string()).last_modified(UNIX_EPOCH - Duration::from_secs(1));
+    /// assert_eq!(resp.headers.get("last-modified"), Some("Thu, 01 Jan 1970 00:00:00 GMT"));
+    /// ```
+    pub fn last_modified(mut self, t: SystemTime) -> Response {
+        self.headers.insert("Last-Modified", common::fmt_http_date(t));
+        self
+    }
+
+    /// return the Response answering the conditional headers of provided request headers
+    ///
+    /// Following RFC 9110 precedence, `If-Match` (or else `If-Unmodified-Since`) is evaluated first
+    /// and fails with `412 Precondition Failed`, then `If-None-Match` (or else `If-Modified-Since`)
+    /// answers `304 Not Modified` to `GET` / `HEAD` requests, `412` to other methods.
+    /// Validators are the `ETag` and `Last-Modified` headers of the Response,
+    /// only successful responses are evaluated.
+    ///
+    /// example:
+    /// ```
+    /// use std::time::{Duration, UNIX_EPOCH};
+    /// use hegel::http::{HeaderMap, Response, StatusCode};
+    ///
+    /// let modified = UNIX_EPOCH + Duration::from_secs(1_600_000_000);
+    /// let resp = Response::new_text("v2".to_string()).etag().last_modified(modified);
+    ///
+    /// let mut req = HeaderMap::new();
+    /// req.insert("If-Match", "\"stale\"");
+    /// assert_eq!(resp.clone().conditional(&req, "PUT").status_code, StatusCode::PRECONDITION_FAILED);
+    ///
+    /// let mut req = HeaderMap::new();
+    /// req.insert("If-Modified-Since", "Sun, 13 Sep 2020 12:26:40 GMT");
+    /// assert_eq!(resp.clone().conditional(&req, "GET").status_code, StatusCode::NOT_MODIFIED);
+    /// assert_eq!(resp.clone().conditional(&req, "POST").status_code, StatusCode::OK);
+    /// ```
+    pub fn conditional(self, req: &HeaderMap, method: &str) -> Response {
+        super::conditional::apply(self, req, method)
+    }
+
+    /// return the Response with its body compressed for provided request headers
+    ///
+    /// ! Remember to enable feature **compression** before using it !
+    ///
+    /// The encoding is negotiated with `Accept-Encoding` (`br`, `gzip` or `deflate`),
+    /// and the compressed body is base64 encoded with `Content-Encoding` and `Vary` set.
+    /// Bodies already encoded, smaller than **http::compress::MIN_SIZE** or of types
+    /// not worth compressing (images, archives...) are left as is.
+    ///
+    /// example:
+    /// ```
+    /// use hegel::http;
+    /// use lambda_runtime::Error;
+    ///
+    /// async fn handler(req: http::Event) -> Result<http::Response, Error> {
+    ///     let items = vec!["{\"id\":42}"; 1000].join(",");
+    ///     Ok(http::Response::new_json(format!("[{}]", items)).compress(&req.payload.headers))
+    /// }
+    /// ```
+    #[cfg(feature = "compression")]
+    pub fn compress(self, req: &HeaderMap) -> Response {
+        super::compress::apply(self, req)
+    }
+
+    /// Get the body bytes, decoding base64
+    fn body_bytes(&self) -> Vec<u8> {
+        match self.is_base64encoded {
+            true => base64::decode(&self.body).unwrap_or_else(|_| self.body.as_bytes().to_vec()),
+            false => self.body.as_bytes().to_vec(),
+        }
+    }
+
+    /// return a Response the browser should save as a file with provided name
+    /// like s struct builder
+    ///
+    /// Sets `Content-Disposition: attachment`, non-ASCII names are encoded following RFC 5987.
+    ///
+    /// example:
+    /// ```
+    /// use hegel::http::Response;
+    ///
+    /// let resp = Response::new_file_named(b"a,b".to_vec(), "résumé.csv".to_string())
+    ///     .attachment("résumé.csv".to_string());
+    /// assert_eq!(
+    ///     resp.headers.get("content-disposition"),
+    ///     Some("attachment; filename=\"r_sum_.csv\"; filename*=UTF-8''r%C3%A9sum%C3%A9.csv")
+    /// );
+    /// ```
+    pub fn attachment(mut self, filename: String) -> Response {
+        self.headers.insert("Content-Disposition", content_disposition("attachment", &filename));
+        self
+    }
+
+    /// return a Response the browser should display, with provided name when saved
+    /// like s struct builder
+    ///
+    /// Sets `Content-Disposition: inline`, non-ASCII names are encoded following RFC 5987.
+    pub fn inline(mut self, filename: String) -> Response {
+        self.headers.insert("Content-Disposition", content_disposition("inline", &filename));
         self
     }
 
@@ -156,4 +450,103 @@ impl Response {
     }
 }
 
+/// Check whether UTF-8 content of unknown type is text, without control characters
+fn is_plain_text(text: &str) -> bool {
+    !text.chars().any(|c| c.is_control() && !matches!(c, '\t' | '\n' | '\r' | '\x0c'))
+}
+
+/// Build a `Content-Disposition` value with an ASCII `filename`
+/// and, when needed, an RFC 5987 `filename*`
+fn content_disposition(kind: &str, filename: &str) -> String {
+    let filename = filename.rsplit(['/', '\\']).next().unwrap_or_default();
+    let fallback: String = filename.chars()
+        .map(|c| match c {
+            ' '..='~' if c != '"' && c != '\\' => c,
+            _ => '_',
+        })
+        .collect();
+    if fallback == filename {
+        return format!("{}; filename=\"{}\"", kind, filename);
+    }
+    let mut encoded = String::new();
+    for b in filename.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9'
+            | b'!' | b'#' | b'$' | b'&' | b'+' | b'-' | b'.' | b'^' | b'_' | b'`' | b'|' | b'~' => encoded.push(b as char),
+            _ => encoded.push_str(&format!("%{:02X}", b)),
+        }
+    }
+    format!("{}; filename=\"{}\"; filename*=UTF-8''{}", kind, fallback, encoded)
+}
+
+/// Types which can be turned into a **Response**,
+/// returned by handlers registered on **hegel::router::Router**
+///
+/// example:
+/// ```
+/// use hegel::http::{IntoResponse, Response, StatusCode};
+///
+/// assert_eq!("hello".into_response(), Response::new_text("hello".to_string()));
+/// assert_eq!((StatusCode::CREATED, "created".to_string()).into_response().status_code, 201);
+///
+/// let resp = Err::<String, _>((StatusCode::NOT_FOUND, "no such item")).into_response();
+/// assert_eq!(resp.status_code, 404);
+/// assert_eq!(resp.body, "no such item");
+/// ```
+pub trait IntoResponse {
+    /// Turn it into a **Response**
+    fn into_response(self) -> Response;
+}
+
+impl IntoResponse for Response {
+    fn into_response(self) -> Response {
+        self
+    }
+}
+
+/// text(UTF-8) Response
+impl IntoResponse for String {
+    fn into_response(self) -> Response {
+        Response::new_text(self)
+    }
+}
+
+/// text(UTF-8) Response
+impl IntoResponse for &'static str {
+    fn into_response(self) -> Response {
+        Response::new_text(self.to_string())
+    }
+}
+
+/// HTTP status Response
+impl IntoResponse for StatusCode {
+    fn into_response(self) -> Response {
+        Response::new_status(self)
+    }
+}
 
+/// empty `200 OK` Response
+impl IntoResponse for () {
+    fn into_response(self) -> Response {
+        Response {
+            status_code: StatusCode::OK,
+            ..Default::default()
+        }
+    }
+}
+
+/// Response with the status code replaced
+impl<T: IntoResponse> IntoResponse for (StatusCode, T) {
+    fn into_response(self) -> Response {
+        self.1.into_response().status_code(self.0)
+    }
+}
+
+impl<T: IntoResponse, E: IntoResponse> IntoResponse for Result<T, E> {
+    fn into_response(self) -> Response {
+        match self {
+            Ok(v) => v.into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+}
@@ -0,0 +1,191 @@
+//! Adapters between **hegel::http** handlers and `tower` services
+//! ! Remember to enable feature **tower** before using it !
+//!
+//! **TowerHandler** serves a `tower::Service<::http::Request<B>>` (like an axum `Router`)
+//! as a hegel HTTP API handler, so the same application and middleware run on Lambda and in containers.
+//!
+//! **HegelService** goes the other way and mounts a hegel handler as a `tower::Service`
+//! of `http` crate requests, for example inside a hyper server.
+
+use super::{Request, Response};
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use bytes::{Buf, Bytes};
+use http_body::{Body as HttpBody, Full};
+use lambda_runtime::LambdaEvent;
+use tower_service::Service;
+
+/// Boxed error type used by the adapters
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+type BoxFuture<T> = Pin<Box<dyn Future<Output = Result<T, BoxError>> + Send>>;
+
+/// Serve a `tower::Service` of `http` crate requests as a hegel HTTP API handler
+///
+/// The request body type `B` is built from the decoded body bytes.
+/// The **lambda_runtime::Context** and **common::RequestContext** are available in the request extensions.
+///
+/// example:
+/// ```rust,no_run
+/// use hegel::http::service::TowerHandler;
+/// use lambda_runtime::{service_fn, Error};
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Error> {
+///     // an axum Router, or any other tower service
+///     let app = service_fn(|req: ::http::Request<Vec<u8>>| async move {
+///         let body = format!("Hello from {}", req.uri().path());
+///         Ok::<_, Error>(::http::Response::new(body))
+///     });
+///     lambda_runtime::run(TowerHandler::new(app)).await?;
+///     Ok(())
+/// }
+/// ```
+pub struct TowerHandler<S, B> {
+    inner: S,
+    _body: PhantomData<fn() -> B>,
+}
+
+impl<S, B> TowerHandler<S, B> {
+    /// new **TowerHandler** wrapping a tower service
+    pub fn new(inner: S) -> Self {
+        TowerHandler {
+            inner,
+            _body: PhantomData,
+        }
+    }
+
+    /// Get the wrapped tower service back
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S: Clone, B> Clone for TowerHandler<S, B> {
+    fn clone(&self) -> Self {
+        TowerHandler::new(self.inner.clone())
+    }
+}
+
+impl<S, B, ResBody> Service<LambdaEvent<Request>> for TowerHandler<S, B>
+where
+    S: Service<::http::Request<B>, Response = ::http::Response<ResBody>> + Clone + Send + 'static,
+    S::Future: Send,
+    S::Error: Into<BoxError>,
+    B: From<Vec<u8>> + Send + 'static,
+    ResBody: HttpBody + Send + 'static,
+    ResBody::Data: Send,
+    ResBody::Error: Into<BoxError>,
+{
+    type Response = Response;
+    type Error = BoxError;
+    type Future = BoxFuture<Response>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, event: LambdaEvent<Request>) -> Self::Future {
+        // the ready service is the one to call, leave a fresh clone in its place
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let req = ::http::Request::<Vec<u8>>::try_from(event.payload).map(|mut req| {
+            req.extensions_mut().insert(event.context);
+            req.map(B::from)
+        });
+        Box::pin(async move {
+            let resp = inner.call(req?).await.map_err(Into::into)?;
+            let (parts, body) = resp.into_parts();
+            let body = collect(body).await?;
+            Ok(::http::Response::from_parts(parts, body).into())
+        })
+    }
+}
+
+/// Mount a hegel HTTP API handler as a `tower::Service` of `http` crate requests
+///
+/// The handler receives a payload format 2.0 request built from the `http` crate request,
+/// with the **lambda_runtime::Context** taken from the request extensions or defaulted.
+///
+/// example:
+/// ```
+/// use hegel::http::{self, service::HegelService};
+/// use lambda_runtime::{service_fn, Error};
+///
+/// async fn func(req: http::Event) -> Result<http::Response, Error> {
+///     Ok(http::Response::new_text(req.payload.path()))
+/// }
+///
+/// // can be served by hyper or mounted in an axum Router
+/// let svc = HegelService::new(service_fn(func));
+/// ```
+#[derive(Debug, Clone)]
+pub struct HegelService<S> {
+    inner: S,
+}
+
+impl<S> HegelService<S> {
+    /// new **HegelService** wrapping a hegel handler service
+    pub fn new(inner: S) -> Self {
+        HegelService { inner }
+    }
+
+    /// Get the wrapped handler service back
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S, B> Service<::http::Request<B>> for HegelService<S>
+where
+    S: Service<LambdaEvent<Request>, Response = Response> + Clone + Send + 'static,
+    S::Future: Send,
+    S::Error: Into<BoxError>,
+    B: HttpBody + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<BoxError>,
+{
+    type Response = ::http::Response<Full<Bytes>>;
+    type Error = BoxError;
+    type Future = BoxFuture<Self::Response>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, req: ::http::Request<B>) -> Self::Future {
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        Box::pin(async move {
+            let (parts, body) = req.into_parts();
+            let context = parts.extensions.get::<lambda_runtime::Context>().cloned().unwrap_or_default();
+            let body = collect(body).await?;
+            let payload = Request::from(::http::Request::from_parts(parts, body));
+            let resp = inner.call(LambdaEvent::new(payload, context)).await.map_err(Into::into)?;
+            let resp = ::http::Response::<Vec<u8>>::try_from(resp)?;
+            Ok(resp.map(|b| Full::new(Bytes::from(b))))
+        })
+    }
+}
+
+/// Read a whole body into memory
+async fn collect<B>(body: B) -> Result<Vec<u8>, BoxError>
+where
+    B: HttpBody,
+    B::Error: Into<BoxError>,
+{
+    let mut body = Box::pin(body);
+    let mut buf = Vec::new();
+    while let Some(chunk) = body.data().await {
+        let mut chunk = chunk.map_err(Into::into)?;
+        while chunk.has_remaining() {
+            let c = chunk.chunk();
+            let n = c.len();
+            buf.extend_from_slice(c);
+            chunk.advance(n);
+        }
+    }
+    Ok(buf)
+}
@@ -0,0 +1,85 @@
+use std::fmt;
+
+/// Ordered `application/x-www-form-urlencoded` key/value pairs
+///
+/// `+` is decoded as a space and `%XX` escapes are decoded.
+/// Repeated keys are all kept, in the order they were sent.
+///
+/// example:
+/// ```
+/// use hegel::http::Pairs;
+///
+/// let pairs = Pairs::parse(b"name=J%C3%BCrgen+Smith&tag=a&tag=b");
+/// assert_eq!(pairs.get("name"), Some("Jürgen Smith"));
+/// assert_eq!(pairs.get_all("tag"), vec!["a", "b"]);
+/// assert_eq!(pairs.iter().count(), 3);
+/// ```
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct Pairs {
+    pairs: Vec<(String, String)>,
+}
+
+impl Pairs {
+    /// Parse urlencoded bytes
+    pub fn parse(input: &[u8]) -> Pairs {
+        Pairs {
+            pairs: form_urlencoded::parse(input).into_owned().collect(),
+        }
+    }
+
+    /// Get the value of the first pair with provided key
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.pairs.iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Get the values of all pairs with provided key
+    pub fn get_all(&self, key: &str) -> Vec<&str> {
+        self.pairs.iter()
+            .filter(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+            .collect()
+    }
+
+    /// Check whether a pair with provided key was sent
+    pub fn contains(&self, key: &str) -> bool {
+        self.pairs.iter().any(|(k, _)| k == key)
+    }
+
+    /// Iterate over all pairs as `(key, value)`
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.pairs.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Get the number of pairs
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    /// Check whether no pair was sent
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+}
+
+impl IntoIterator for Pairs {
+    type Item = (String, String);
+    type IntoIter = std::vec::IntoIter<(String, String)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.pairs.into_iter()
+    }
+}
+
+/// Error returned when a query string does not match the target type
+#[derive(Debug, Clone)]
+pub struct QueryError(pub serde_html_form::de::Error);
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid query string: {}", self.0)
+    }
+}
+
+impl std::error::Error for QueryError {}
@@ -0,0 +1,90 @@
+/// Check whether a media type is textual, so its body can be sent without base64
+///
+/// example:
+/// ```
+/// use hegel::http::utils::mime;
+///
+/// assert!(mime::is_text("text/csv"));
+/// assert!(mime::is_text("application/problem+json; charset=utf-8"));
+/// assert!(!mime::is_text("image/png"));
+/// ```
+pub fn is_text(mime: &str) -> bool {
+    let mime = mime.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+    if mime.starts_with("text/") {
+        return true;
+    }
+    match mime.strip_prefix("application/") {
+        Some(sub) => matches!(
+            sub,
+            "json" | "javascript" | "ecmascript" | "xml" | "x-www-form-urlencoded" | "graphql" | "yaml" | "x-yaml" | "toml"
+        ) || sub.ends_with("+json") || sub.ends_with("+xml"),
+        None => mime == "image/svg+xml",
+    }
+}
+
+/// Guess a media type from a file name extension
+///
+/// example:
+/// ```
+/// use hegel::http::utils::mime;
+///
+/// assert_eq!(mime::from_filename("report.PDF"), Some("application/pdf"));
+/// assert_eq!(mime::from_filename("styles/site.css"), Some("text/css"));
+/// assert_eq!(mime::from_filename("README"), None);
+/// ```
+pub fn from_filename(filename: &str) -> Option<&'static str> {
+    let name = filename.rsplit(['/', '\\']).next().unwrap_or_default();
+    let (_, ext) = name.rsplit_once('.')?;
+    let mime = match ext.to_ascii_lowercase().as_str() {
+        "html" | "htm" => "text/html",
+        "css" => "text/css",
+        "js" | "mjs" => "text/javascript",
+        "txt" | "text" | "log" => "text/plain",
+        "csv" => "text/csv",
+        "md" | "markdown" => "text/markdown",
+        "ics" => "text/calendar",
+        "xml" => "application/xml",
+        "json" | "map" => "application/json",
+        "jsonld" => "application/ld+json",
+        "yaml" | "yml" => "application/yaml",
+        "toml" => "application/toml",
+        "svg" => "image/svg+xml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "avif" => "image/avif",
+        "ico" => "image/vnd.microsoft.icon",
+        "bmp" => "image/bmp",
+        "tif" | "tiff" => "image/tiff",
+        "pdf" => "application/pdf",
+        "zip" => "application/zip",
+        "gz" => "application/gzip",
+        "tar" => "application/x-tar",
+        "7z" => "application/x-7z-compressed",
+        "wasm" => "application/wasm",
+        "epub" => "application/epub+zip",
+        "rtf" => "application/rtf",
+        "doc" => "application/msword",
+        "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        "xls" => "application/vnd.ms-excel",
+        "xlsx" => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        "ppt" => "application/vnd.ms-powerpoint",
+        "pptx" => "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+        "mp3" => "audio/mpeg",
+        "wav" => "audio/wav",
+        "oga" | "ogg" => "audio/ogg",
+        "m4a" => "audio/mp4",
+        "flac" => "audio/flac",
+        "mp4" => "video/mp4",
+        "webm" => "video/webm",
+        "ogv" => "video/ogg",
+        "mov" => "video/quicktime",
+        "woff" => "font/woff",
+        "woff2" => "font/woff2",
+        "ttf" => "font/ttf",
+        "otf" => "font/otf",
+        _ => return None,
+    };
+    Some(mime)
+}
@@ -1 +1,2 @@
-pub mod status_code;
\ No newline at end of file
+pub mod status_code;
+pub mod mime;
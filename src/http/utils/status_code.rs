@@ -1,60 +1,224 @@
+use std::fmt;
+use serde::{Serialize, Deserialize};
 
+/// HTTP status code, always in the `100..=599` range
+///
+/// Serialized as a bare number, like the `statusCode` field of API Gateway responses.
+///
+/// example:
+/// ```
+/// use hegel::http::StatusCode;
+///
+/// let s = StatusCode::try_from(404).unwrap();
+/// assert_eq!(s, StatusCode::NOT_FOUND);
+/// assert!(s.is_client_error());
+/// assert_eq!(s.canonical_reason(), Some("Not Found"));
+/// assert_eq!(s.to_string(), "404 Not Found");
+/// assert_eq!(u16::from(s), 404);
+///
+/// assert!(StatusCode::try_from(600).is_err());
+/// assert_eq!(StatusCode::try_from(299).unwrap().to_string(), "299");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "u16", into = "u16")]
+pub struct StatusCode(u16);
+
+/// Error returned when a number is not a valid HTTP status code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidStatusCode(pub u16);
+
+impl fmt::Display for InvalidStatusCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid HTTP status code {}, expected 100-599", self.0)
+    }
+}
+
+impl std::error::Error for InvalidStatusCode {}
+
+impl StatusCode {
+    /// new **StatusCode**, returns an error when out of the `100..=599` range
+    pub fn from_u16(s: u16) -> Result<StatusCode, InvalidStatusCode> {
+        match s {
+            100..=599 => Ok(StatusCode(s)),
+            _ => Err(InvalidStatusCode(s)),
+        }
+    }
+
+    /// Get the status code as a number
+    pub fn as_u16(&self) -> u16 {
+        self.0
+    }
+
+    /// Get the reason phrase of registered status codes
+    ///
+    /// example: `Not Found`
+    pub fn canonical_reason(&self) -> Option<&'static str> {
+        reason(self.0)
+    }
+
+    /// `1xx`
+    pub fn is_informational(&self) -> bool {
+        (100..200).contains(&self.0)
+    }
+
+    /// `2xx`
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.0)
+    }
+
+    /// `3xx`
+    pub fn is_redirection(&self) -> bool {
+        (300..400).contains(&self.0)
+    }
+
+    /// `4xx`
+    pub fn is_client_error(&self) -> bool {
+        (400..500).contains(&self.0)
+    }
+
+    /// `5xx`
+    pub fn is_server_error(&self) -> bool {
+        (500..600).contains(&self.0)
+    }
+}
+
+/// `200 OK`
+impl Default for StatusCode {
+    fn default() -> Self {
+        StatusCode::OK
+    }
+}
+
+/// `404 Not Found`, or only the number for unregistered status codes
+impl fmt::Display for StatusCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.canonical_reason() {
+            Some(r) => write!(f, "{} {}", self.0, r),
+            None => write!(f, "{}", self.0),
+        }
+    }
+}
+
+impl TryFrom<u16> for StatusCode {
+    type Error = InvalidStatusCode;
+
+    fn try_from(s: u16) -> Result<Self, Self::Error> {
+        StatusCode::from_u16(s)
+    }
+}
+
+impl From<StatusCode> for u16 {
+    fn from(s: StatusCode) -> Self {
+        s.0
+    }
+}
+
+impl PartialEq<u16> for StatusCode {
+    fn eq(&self, other: &u16) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialEq<StatusCode> for u16 {
+    fn eq(&self, other: &StatusCode) -> bool {
+        *self == other.0
+    }
+}
+
+macro_rules! status_codes {
+    ($($code:literal $name:ident $reason:literal;)*) => {
+        impl StatusCode {
+            $(
+                #[doc = concat!("`", $code, " ", $reason, "`")]
+                pub const $name: StatusCode = StatusCode($code);
+            )*
+        }
+
+        fn reason(s: u16) -> Option<&'static str> {
+            match s {
+                $($code => Some($reason),)*
+                _ => None,
+            }
+        }
+
+        fn status_line(s: u16) -> Option<&'static str> {
+            match s {
+                $($code => Some(concat!($code, " ", $reason)),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+status_codes! {
+    100 CONTINUE "Continue";
+    101 SWITCHING_PROTOCOLS "Switching Protocols";
+    102 PROCESSING "Processing";
+    103 EARLY_HINTS "Early Hints";
+    200 OK "OK";
+    201 CREATED "Created";
+    202 ACCEPTED "Accepted";
+    203 NON_AUTHORITATIVE_INFORMATION "Non-Authoritative Information";
+    204 NO_CONTENT "No Content";
+    205 RESET_CONTENT "Reset Content";
+    206 PARTIAL_CONTENT "Partial Content";
+    207 MULTI_STATUS "Multi-Status";
+    208 ALREADY_REPORTED "Already Reported";
+    226 IM_USED "IM Used";
+    300 MULTIPLE_CHOICES "Multiple Choices";
+    301 MOVED_PERMANENTLY "Moved Permanently";
+    302 FOUND "Found";
+    303 SEE_OTHER "See Other";
+    304 NOT_MODIFIED "Not Modified";
+    305 USE_PROXY "Use Proxy";
+    307 TEMPORARY_REDIRECT "Temporary Redirect";
+    308 PERMANENT_REDIRECT "Permanent Redirect";
+    400 BAD_REQUEST "Bad Request";
+    401 UNAUTHORIZED "Unauthorized";
+    402 PAYMENT_REQUIRED "Payment Required";
+    403 FORBIDDEN "Forbidden";
+    404 NOT_FOUND "Not Found";
+    405 METHOD_NOT_ALLOWED "Method Not Allowed";
+    406 NOT_ACCEPTABLE "Not Acceptable";
+    407 PROXY_AUTHENTICATION_REQUIRED "Proxy Authentication Required";
+    408 REQUEST_TIMEOUT "Request Timeout";
+    409 CONFLICT "Conflict";
+    410 GONE "Gone";
+    411 LENGTH_REQUIRED "Length Required";
+    412 PRECONDITION_FAILED "Precondition Failed";
+    413 PAYLOAD_TOO_LARGE "Payload Too Large";
+    414 URI_TOO_LONG "URI Too Long";
+    415 UNSUPPORTED_MEDIA_TYPE "Unsupported Media Type";
+    416 RANGE_NOT_SATISFIABLE "Range Not Satisfiable";
+    417 EXPECTATION_FAILED "Expectation Failed";
+    418 IM_A_TEAPOT "I'm a teapot";
+    421 MISDIRECTED_REQUEST "Misdirected Request";
+    422 UNPROCESSABLE_ENTITY "Unprocessable Entity";
+    423 LOCKED "Locked";
+    424 FAILED_DEPENDENCY "Failed Dependency";
+    425 TOO_EARLY "Too Early";
+    426 UPGRADE_REQUIRED "Upgrade Required";
+    428 PRECONDITION_REQUIRED "Precondition Required";
+    429 TOO_MANY_REQUESTS "Too Many Requests";
+    431 REQUEST_HEADER_FIELDS_TOO_LARGE "Request Header Fields Too Large";
+    451 UNAVAILABLE_FOR_LEGAL_REASONS "Unavailable For Legal Reasons";
+    500 INTERNAL_SERVER_ERROR "Internal Server Error";
+    501 NOT_IMPLEMENTED "Not Implemented";
+    502 BAD_GATEWAY "Bad Gateway";
+    503 SERVICE_UNAVAILABLE "Service Unavailable";
+    504 GATEWAY_TIMEOUT "Gateway Timeout";
+    505 HTTP_VERSION_NOT_SUPPORTED "HTTP Version Not Supported";
+    506 VARIANT_ALSO_NEGOTIATES "Variant Also Negotiates";
+    507 INSUFFICIENT_STORAGE "Insufficient Storage";
+    508 LOOP_DETECTED "Loop Detected";
+    510 NOT_EXTENDED "Not Extended";
+    511 NETWORK_AUTHENTICATION_REQUIRED "Network Authentication Required";
+}
+
+/// Get the status line of registered status codes
+///
+/// example: `404 Not Found`
+#[deprecated(note = "use `StatusCode::canonical_reason` or the `Display` of `StatusCode`")]
 pub fn meaning(sc: u16) -> Option<&'static str> {
-    return match sc {
-        100 => Some("100 Continue"),
-        101 => Some("101 Switching Protocols"),
-        103 => Some("103 Early Hints"),
-        200 => Some("200 OK"),
-        201 => Some("201 Created"),
-        202 => Some("202 Accepted"),
-        203 => Some("203 Non-Authoritative Information"),
-        204 => Some("204 No Content"),
-        205 => Some("205 Reset Content"),
-        206 => Some("206 Partial Content"),
-        300 => Some("300 Multiple Choices"),
-        301 => Some("301 Moved Permanently"),
-        302 => Some("302 Found"),
-        303 => Some("303 See Other"),
-        304 => Some("304 Not Modified"),
-        307 => Some("307 Temporary Redirect"),
-        308 => Some("308 Permanent Redirect"),
-        400 => Some("400 Bad Request"),
-        401 => Some("401 Unauthorized"),
-        402 => Some("402 Payment Required"),
-        403 => Some("403 Forbidden"),
-        404 => Some("404 Not Found"),
-        405 => Some("405 Method Not Allowed"),
-        406 => Some("406 Not Acceptable"),
-        407 => Some("407 Proxy Authentication Required"),
-        408 => Some("408 Request Timeout"),
-        409 => Some("409 Conflict"),
-        410 => Some("410 Gone"),
-        411 => Some("411 Length Required"),
-        412 => Some("412 Precondition Failed"),
-        413 => Some("413 Payload Too Large"),
-        414 => Some("414 URI Too Long"),
-        415 => Some("415 Unsupported Media Type"),
-        416 => Some("416 Range Not Satisfiable"),
-        417 => Some("417 Expectation Failed"),
-        418 => Some("418 I'm a teapot"),
-        422 => Some("422 Unprocessable Entity"),
-        425 => Some("425 Too Early"),
-        426 => Some("426 Upgrade Required"),
-        428 => Some("428 Precondition Required"),
-        429 => Some("429 Too Many Requests"),
-        431 => Some("431 Request Header Fields Too Large"),
-        451 => Some("451 Unavailable For Legal Reasons"),
-        500 => Some("500 Internal Server Error"),
-        501 => Some("501 Not Implemented"),
-        502 => Some("502 Bad Gateway"),
-        503 => Some("503 Service Unavailable"),
-        504 => Some("504 Gateway Timeout"),
-        505 => Some("505 HTTP Version Not Supported"),
-        506 => Some("506 Variant Also Negotiates"),
-        507 => Some("507 Insufficient Storage"),
-        508 => Some("508 Loop Detected"),
-        510 => Some("510 Not Extended"),
-        511 => Some("511 Network Authentication Required"),
-        _ => None
-    }
-}
\ No newline at end of file
+    status_line(sc)
+}
@@ -0,0 +1,31 @@
+pub mod req;
+pub mod resp;
+
+pub use resp::Response;
+pub use req::Request;
+
+use lambda_runtime::LambdaEvent;
+
+/// Event for lambda_runtime service function
+/// Used for building API Gateway Lambda proxy integrations for HTTP APIs
+/// configured with payload format 1.0
+///
+/// example:
+/// ```rust,no_run
+/// use lambda_runtime::{service_fn, Error};
+/// use hegel::http::{self, v1};
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Error> {
+///     let func = service_fn(func);
+///     lambda_runtime::run(func).await?;
+///     Ok(())
+/// }
+///
+/// async fn func(req: v1::Event) -> Result<v1::Response, Error> {
+///     // ...
+///     Ok(v1::Response::new_status(http::StatusCode::OK))
+/// }
+/// ```
+///
+pub type Event = LambdaEvent<Request>;
@@ -0,0 +1,225 @@
+use crate::common;
+use crate::http::{CookieJar, HeaderMap};
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use serde::{Serialize, Deserialize};
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, TimeZone, Utc};
+
+pub use common::ParseBodyError;
+
+/// **lambda_runtime** service payload type (format 1.0)
+/// Used for building API Gateway Lambda proxy integrations for HTTP APIs
+///
+/// example:
+/// ```
+/// use hegel::http::{self, v1};
+/// use lambda_runtime::{Error, LambdaEvent};
+///
+/// async fn handler(req: LambdaEvent<v1::Request>) -> Result<v1::Response, Error> {
+///     //...
+///     Ok(v1::Response::new_status(http::StatusCode::OK))
+/// }
+/// ```
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    #[serde(default)]
+    pub version: String,
+    pub resource: String,
+    pub path: String,
+    pub http_method: String,
+    pub headers: Option<HashMap<String, String>>,
+    pub multi_value_headers: Option<HashMap<String, Vec<String>>>,
+    pub query_string_parameters: Option<HashMap<String, String>>,
+    pub multi_value_query_string_parameters: Option<HashMap<String, Vec<String>>>,
+    pub request_context: common::RequestContextV1,
+    pub path_parameters: Option<HashMap<String, String>>,
+    pub stage_variables: Option<HashMap<String, String>>,
+    pub body: Option<String>,
+    #[serde(rename = "isBase64Encoded")]
+    pub is_base64encoded: bool,
+}
+
+impl Request {
+    /// Get user request body as String (UTF-8)
+    pub fn body(&self) -> Result<Option<String>, ParseBodyError> {
+        common::decode_body(&self.body, self.is_base64encoded)
+    }
+
+    /// Get user request body as binary
+    pub fn body_binary(&self) -> Result<Option<Vec<u8>>, base64::DecodeError> {
+        common::decode_body_binary(&self.body, self.is_base64encoded)
+    }
+
+    /// Get HTTP request path
+    ///
+    /// example: `/foo/bar`
+    pub fn path(&self) -> String {
+        self.path.clone()
+    }
+
+    /// Get user request cookies (parsed from the `Cookie` headers)
+    pub fn cookies(&self) -> CookieJar {
+        match &self.multi_value_headers {
+            Some(m) => CookieJar::parse(header_values(m, "cookie")),
+            None => CookieJar::parse(self.header("cookie").map(|c| c.as_str())),
+        }
+    }
+
+    /// Get user request headers
+    ///
+    /// Headers sent multiple times keep all their values
+    pub fn headers(&self) -> HeaderMap {
+        match &self.multi_value_headers {
+            Some(m) => m.iter()
+                .flat_map(|(k, v)| v.iter().map(move |v| (k.as_str(), v.as_str())))
+                .collect(),
+            None => self.headers.clone().unwrap_or_default().into(),
+        }
+    }
+
+    /// Get user request headers with all values of repeated headers
+    pub fn multi_value_headers(&self) -> HashMap<String, Vec<String>> {
+        self.multi_value_headers.clone().unwrap_or_default()
+    }
+
+    /// Get user request queries
+    ///
+    /// example:
+    /// URL: `https://iochen.com/foor/bar?a=1&b=2`
+    /// Result HashMap:
+    /// ```text
+    /// "a" -> "1"
+    /// "b" -> "2"
+    /// ```
+    pub fn queries(&self) -> Option<HashMap<String, String>> {
+        self.query_string_parameters.clone()
+    }
+
+    /// Get user request queries with all values of repeated keys
+    ///
+    /// example:
+    /// URL: `https://iochen.com/foor/bar?a=1&a=2`
+    /// Result HashMap:
+    /// ```text
+    /// "a" -> ["1", "2"]
+    /// ```
+    pub fn multi_value_queries(&self) -> Option<HashMap<String, Vec<String>>> {
+        self.multi_value_query_string_parameters.clone()
+    }
+
+    /// Get route params
+    ///
+    /// example:
+    /// Route: `GET /foo/{proxy+}`
+    /// Request: `GET /foo/bar`
+    /// Result HashMap:
+    /// ```text
+    /// "proxy" -> "bar"
+    /// ```
+    pub fn params(&self) -> Option<HashMap<String, String>> {
+        self.path_parameters.clone()
+    }
+
+    /// Get API Gateway stage
+    ///
+    /// example: `$default`
+    pub fn stage(&self) -> String {
+        self.request_context.stage.clone()
+    }
+
+    /// Get request datetime (Instant)
+    pub fn time(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_millis(self.request_context.request_time_epoch)
+    }
+
+    /// Get request datetime with **chrono::DateTime** type output
+    /// ! Remember to enable feature **chrono** before using it !
+    #[cfg(feature = "chrono")]
+    pub fn time_chrono(&self) -> DateTime<Utc> {
+        Utc.timestamp_millis(self.request_context.request_time_epoch as i64)
+    }
+
+    /// Get user request method
+    ///
+    /// example: `GET`, `POST`, `DELETE` ...
+    pub fn method(&self) -> String {
+        self.http_method.clone()
+    }
+
+    /// Get user request IP
+    pub fn ip(&self) -> String {
+        self.request_context.identity.source_ip.clone()
+    }
+
+    /// Get user request User-Agent
+    pub fn ua(&self) -> String {
+        self.request_context.identity.user_agent.clone().unwrap_or_default()
+    }
+
+    /// Get user request HTTP protocol
+    ///
+    /// example: `HTTP/1.1`
+    pub fn protocol(&self) -> String {
+        self.request_context.protocol.clone().unwrap_or_default()
+    }
+
+    /// Get the API Gateway resource the request matched
+    ///
+    /// example: `/foo/{proxy+}`
+    pub fn resource(&self) -> String {
+        self.resource.clone()
+    }
+
+    /// Get the resource path of the request context
+    ///
+    /// example: `/foo/{proxy+}`
+    pub fn resource_path(&self) -> Option<String> {
+        self.request_context.resource_path.clone()
+    }
+
+    /// Get caller identity
+    pub fn identity(&self) -> &common::Identity {
+        &self.request_context.identity
+    }
+
+    /// Get the API key the caller used (REST APIs with API keys only)
+    pub fn api_key(&self) -> Option<String> {
+        self.request_context.identity.api_key.clone()
+    }
+
+    /// Get the principal identifier of the IAM caller
+    pub fn caller(&self) -> Option<String> {
+        self.request_context.identity.caller.clone()
+    }
+
+    /// Get the ARN of the IAM caller
+    pub fn user_arn(&self) -> Option<String> {
+        self.request_context.identity.user_arn.clone()
+    }
+
+    /// Get the Amazon Cognito identity ID of the caller
+    pub fn cognito_identity_id(&self) -> Option<String> {
+        self.request_context.identity.cognito_identity_id.clone()
+    }
+
+    /// Get the context returned by the authorizer
+    pub fn authorizer(&self) -> Option<&HashMap<String, serde_json::Value>> {
+        self.request_context.authorizer.as_ref()
+    }
+
+    fn header(&self, name: &str) -> Option<&String> {
+        self.headers.as_ref()?
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v)
+    }
+}
+
+fn header_values<'a>(m: &'a HashMap<String, Vec<String>>, name: &'a str) -> impl Iterator<Item = &'a str> {
+    m.iter()
+        .filter(move |(k, _)| k.eq_ignore_ascii_case(name))
+        .flat_map(|(_, v)| v.iter().map(|v| v.as_str()))
+}
@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+use serde::{Serialize, Deserialize};
+
+/// **lambda_runtime** service function return payload type (format 1.0)
+/// Used for building API Gateway Lambda proxy integrations for HTTP APIs
+///
+/// Any **http::Response** can be turned into it, so the same builders work for both formats.
+/// Its cookies become `Set-Cookie` multi-value headers.
+///
+/// example:
+/// ```
+/// use hegel::http::{self, v1};
+/// use lambda_runtime::Error;
+///
+/// async fn handler(req: v1::Event) -> Result<v1::Response, Error> {
+///     //...
+///     Ok(http::Response::new_status(http::StatusCode::OK).into())
+/// }
+/// ```
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Response {
+    #[serde(rename = "isBase64Encoded")]
+    pub is_base64encoded: bool,
+    pub status_code: u16,
+    pub body: String,
+    pub headers: HashMap<String, String>,
+    pub multi_value_headers: HashMap<String, Vec<String>>,
+}
+
+impl Response {
+    /// return a HTTP status as Response
+    pub fn new_status(s: crate::http::StatusCode) -> Response {
+        crate::http::Response::new_status(s).into()
+    }
+
+    /// return a HTTP status number as Response,
+    /// or an error when it is not a valid status code (`100..=599`)
+    pub fn try_new_status(s: u16) -> Result<Response, crate::http::InvalidStatusCode> {
+        crate::http::Response::try_new_status(s).map(Into::into)
+    }
+
+    /// return a Response with provided header added
+    /// like s struct builder
+    pub fn header(mut self, k: String, v: String) -> Response {
+        self.headers.insert(k, v);
+        self
+    }
+
+    /// return a Response with provided value appended to a multi-value header
+    /// like s struct builder
+    pub fn multi_value_header(mut self, k: String, v: String) -> Response {
+        self.multi_value_headers.entry(k).or_default().push(v);
+        self
+    }
+}
+
+impl From<crate::http::Response> for Response {
+    fn from(r: crate::http::Response) -> Self {
+        let mut multi_value_headers = HashMap::new();
+        if !r.cookies.is_empty() {
+            multi_value_headers.insert("Set-Cookie".to_string(), r.cookies);
+        }
+        Response {
+            is_base64encoded: r.is_base64encoded,
+            status_code: r.status_code.into(),
+            body: r.body,
+            headers: r.headers.into(),
+            multi_value_headers,
+        }
+    }
+}
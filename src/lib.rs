@@ -1,3 +1,9 @@
 pub mod auth;
 pub mod http;
+pub mod rest;
+pub mod websocket;
+pub mod alb;
+pub mod function_url;
+pub mod router;
+pub mod middleware;
 pub mod common;
\ No newline at end of file
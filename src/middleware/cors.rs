@@ -0,0 +1,310 @@
+use super::{BoxFuture, Middleware, Next};
+use crate::http::{Event, HeaderMap, Response, StatusCode};
+use crate::http::header::add_vary;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// CORS middleware answering preflight requests and adding `Access-Control-*` headers
+///
+/// Preflight requests (`OPTIONS` with `Origin` and `Access-Control-Request-Method`)
+/// are answered with `204 No Content` without calling the handler.
+/// Other requests from an allowed origin get the CORS headers added to their response.
+/// `Vary: Origin` is added whenever the answer depends on the request origin.
+///
+/// example:
+/// ```
+/// use std::time::Duration;
+/// use lambda_runtime::{Context, LambdaEvent};
+/// use hegel::{http, router::Router, middleware::Cors};
+///
+/// async fn items(_: http::Event) -> &'static str {
+///     "[]"
+/// }
+///
+/// let cors = Cors::new()
+///     .allow_origin("https://example.com")
+///     .allow_origin("https://*.example.com")
+///     .allow_methods(["GET", "POST"])
+///     .allow_headers(["Content-Type", "Authorization"])
+///     .allow_credentials(true)
+///     .max_age(Duration::from_secs(600));
+/// let router = Router::new().route("GET /items", items).layer(cors);
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let mut req = http::Request::default();
+/// req.request_context.http.method = "OPTIONS".to_string();
+/// req.request_context.http.path = "/items".to_string();
+/// req.headers.insert("Origin", "https://app.example.com");
+/// req.headers.insert("Access-Control-Request-Method", "POST");
+/// let resp = router.handle(LambdaEvent::new(req, Context::default())).await.unwrap();
+/// assert_eq!(resp.status_code, 204);
+/// assert_eq!(resp.headers.get("access-control-allow-origin"), Some("https://app.example.com"));
+/// assert_eq!(resp.headers.get("access-control-allow-methods"), Some("GET, POST"));
+/// assert_eq!(resp.headers.get("access-control-max-age"), Some("600"));
+///
+/// let mut req = http::Request::default();
+/// req.request_context.http.method = "GET".to_string();
+/// req.request_context.http.path = "/items".to_string();
+/// req.headers.insert("Origin", "https://evil.com");
+/// let resp = router.handle(LambdaEvent::new(req, Context::default())).await.unwrap();
+/// assert_eq!(resp.status_code, 200);
+/// assert_eq!(resp.headers.get("access-control-allow-origin"), None);
+/// assert_eq!(resp.headers.get("vary"), Some("Origin"));
+/// # });
+/// ```
+#[derive(Debug, Clone)]
+pub struct Cors {
+    any_origin: bool,
+    origins: Vec<String>,
+    methods: Vec<String>,
+    any_header: bool,
+    headers: Vec<String>,
+    expose_headers: Vec<String>,
+    credentials: bool,
+    max_age: Option<Duration>,
+}
+
+impl Cors {
+    /// new **Cors** allowing no origin,
+    /// the `GET`, `HEAD`, `PUT`, `PATCH`, `POST` and `DELETE` methods and no extra header
+    pub fn new() -> Cors {
+        Cors {
+            any_origin: false,
+            origins: Vec::new(),
+            methods: ["GET", "HEAD", "PUT", "PATCH", "POST", "DELETE"].iter().map(|m| m.to_string()).collect(),
+            any_header: false,
+            headers: Vec::new(),
+            expose_headers: Vec::new(),
+            credentials: false,
+            max_age: None,
+        }
+    }
+
+    /// new **Cors** allowing any origin and header with the default methods, without credentials
+    pub fn permissive() -> Cors {
+        Cors::new().allow_any_origin().allow_any_header()
+    }
+
+    /// return a Cors with provided origin allowed
+    /// like s struct builder
+    ///
+    /// `*` in the origin matches any characters except `/`,
+    /// like `https://*.example.com`
+    pub fn allow_origin<S: Into<String>>(mut self, origin: S) -> Cors {
+        self.origins.push(origin.into());
+        self
+    }
+
+    /// return a Cors allowing every origin
+    /// like s struct builder
+    ///
+    /// Answers `Access-Control-Allow-Origin: *`.
+    ///
+    /// # Panics
+    /// Panics if credentials are allowed, list the trusted origins with `allow_origin` instead.
+    ///
+    /// ```should_panic
+    /// use hegel::middleware::Cors;
+    ///
+    /// let cors = Cors::new().allow_credentials(true).allow_any_origin();
+    /// ```
+    pub fn allow_any_origin(mut self) -> Cors {
+        assert!(!self.credentials, "Cors can not allow credentials for any origin");
+        self.any_origin = true;
+        self
+    }
+
+    /// return a Cors with provided methods allowed, replacing the default ones
+    /// like s struct builder
+    pub fn allow_methods<I: IntoIterator<Item = S>, S: Into<String>>(mut self, methods: I) -> Cors {
+        self.methods = methods.into_iter().map(|m| m.into().to_ascii_uppercase()).collect();
+        self
+    }
+
+    /// return a Cors with provided request headers allowed
+    /// like s struct builder
+    pub fn allow_headers<I: IntoIterator<Item = S>, S: Into<String>>(mut self, headers: I) -> Cors {
+        self.headers.extend(headers.into_iter().map(Into::into));
+        self
+    }
+
+    /// return a Cors allowing every request header asked in a preflight
+    /// like s struct builder
+    pub fn allow_any_header(mut self) -> Cors {
+        self.any_header = true;
+        self
+    }
+
+    /// return a Cors with provided response headers exposed to the browser
+    /// like s struct builder
+    pub fn expose_headers<I: IntoIterator<Item = S>, S: Into<String>>(mut self, headers: I) -> Cors {
+        self.expose_headers.extend(headers.into_iter().map(Into::into));
+        self
+    }
+
+    /// return a Cors allowing credentials (cookies, `Authorization`) or not
+    /// like s struct builder
+    ///
+    /// # Panics
+    /// Panics if credentials are allowed together with `allow_any_origin`,
+    /// which would let every website read the user's responses.
+    ///
+    /// ```should_panic
+    /// use hegel::middleware::Cors;
+    ///
+    /// let cors = Cors::permissive().allow_credentials(true);
+    /// ```
+    pub fn allow_credentials(mut self, credentials: bool) -> Cors {
+        assert!(!(credentials && self.any_origin), "Cors can not allow credentials for any origin");
+        self.credentials = credentials;
+        self
+    }
+
+    /// return a Cors with provided preflight cache duration
+    /// like s struct builder
+    pub fn max_age(mut self, max_age: Duration) -> Cors {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Check whether an origin is allowed
+    ///
+    /// example:
+    /// ```
+    /// use hegel::middleware::Cors;
+    ///
+    /// let cors = Cors::new().allow_origin("https://*.example.com");
+    /// assert!(cors.is_allowed("https://app.example.com"));
+    /// assert!(!cors.is_allowed("https://example.com"));
+    /// assert!(!cors.is_allowed("https://evil.com/.example.com"));
+    /// ```
+    pub fn is_allowed(&self, origin: &str) -> bool {
+        self.any_origin || self.origins.iter().any(|o| matches_pattern(o, origin))
+    }
+
+    /// Get the `Access-Control-Allow-Origin` value for a request origin
+    fn allow_origin_value(&self, origin: &str) -> Option<String> {
+        if self.any_origin {
+            Some("*".to_string())
+        } else if self.is_allowed(origin) {
+            Some(origin.to_string())
+        } else {
+            None
+        }
+    }
+
+    /// Whether answers depend on the request origin
+    fn varies(&self) -> bool {
+        !self.any_origin
+    }
+
+    fn preflight(&self, req: &HeaderMap, origin: &str) -> Response {
+        let mut resp = Response {
+            status_code: StatusCode::NO_CONTENT,
+            ..Default::default()
+        };
+        let mut vary = vec!["Access-Control-Request-Method", "Access-Control-Request-Headers"];
+        if self.varies() {
+            vary.insert(0, "Origin");
+        }
+        add_vary(&mut resp.headers, &vary);
+        let allow_origin = match self.allow_origin_value(origin) {
+            Some(o) => o,
+            None => return resp,
+        };
+        resp.headers.insert("Access-Control-Allow-Origin", allow_origin);
+        resp.headers.insert("Access-Control-Allow-Methods", self.methods.join(", "));
+        let headers = match self.any_header {
+            true => req.get_list("access-control-request-headers").join(", "),
+            false => self.headers.join(", "),
+        };
+        if !headers.is_empty() {
+            resp.headers.insert("Access-Control-Allow-Headers", headers);
+        }
+        if self.credentials {
+            resp.headers.insert("Access-Control-Allow-Credentials", "true");
+        }
+        if let Some(max_age) = self.max_age {
+            resp.headers.insert("Access-Control-Max-Age", max_age.as_secs().to_string());
+        }
+        resp
+    }
+
+    fn decorate(&self, resp: &mut Response, origin: &str) {
+        if self.varies() {
+            add_vary(&mut resp.headers, &["Origin"]);
+        }
+        let allow_origin = match self.allow_origin_value(origin) {
+            Some(o) => o,
+            None => return,
+        };
+        resp.headers.insert("Access-Control-Allow-Origin", allow_origin);
+        if self.credentials {
+            resp.headers.insert("Access-Control-Allow-Credentials", "true");
+        }
+        if !self.expose_headers.is_empty() {
+            resp.headers.insert("Access-Control-Expose-Headers", self.expose_headers.join(", "));
+        }
+    }
+}
+
+impl Default for Cors {
+    fn default() -> Self {
+        Cors::new()
+    }
+}
+
+impl Middleware for Cors {
+    fn handle(self: Arc<Self>, event: Event, next: Next) -> BoxFuture {
+        let headers = &event.payload.headers;
+        let origin = match headers.origin() {
+            Some(origin) => origin.to_string(),
+            None => {
+                let fut = next.run(event);
+                return Box::pin(async move {
+                    let mut resp = fut.await?;
+                    if self.varies() {
+                        add_vary(&mut resp.headers, &["Origin"]);
+                    }
+                    Ok(resp)
+                });
+            }
+        };
+        if event.payload.method().eq_ignore_ascii_case("OPTIONS") && headers.contains("access-control-request-method") {
+            let resp = self.preflight(headers, &origin);
+            return Box::pin(async move { Ok(resp) });
+        }
+        let fut = next.run(event);
+        Box::pin(async move {
+            let mut resp = fut.await?;
+            self.decorate(&mut resp, &origin);
+            Ok(resp)
+        })
+    }
+}
+
+/// Match an origin against an allowed origin where `*` matches any characters except `/`
+fn matches_pattern(pattern: &str, origin: &str) -> bool {
+    let pattern = pattern.to_ascii_lowercase();
+    let origin = origin.to_ascii_lowercase();
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let mut rest = match origin.strip_prefix(first) {
+        Some(rest) => rest,
+        None => return false,
+    };
+    let parts: Vec<&str> = parts.collect();
+    for (i, part) in parts.iter().enumerate() {
+        let last = i + 1 == parts.len();
+        let found = match last {
+            true if rest.ends_with(part) => Some(rest.len() - part.len()),
+            true => None,
+            false => rest.find(part),
+        };
+        match found {
+            Some(at) if !rest[..at].contains('/') && !rest[..at].is_empty() => rest = &rest[at + part.len()..],
+            _ => return false,
+        }
+    }
+    rest.is_empty()
+}
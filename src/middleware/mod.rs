@@ -0,0 +1,386 @@
+//! Middlewares running around **hegel::router::Router** handlers
+//!
+//! Middlewares are added with `Router::layer` and run in a defined order:
+//! the first one added is the outermost, so its `before` runs first
+//! and its `after` / `on_error` run last.
+//!
+//! example:
+//! ```rust,no_run
+//! use lambda_runtime::Error;
+//! use hegel::{http, router::Router};
+//! use hegel::middleware::{CatchPanic, Logger, RequestId, SetHeaders};
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Error> {
+//!     let router = Router::new()
+//!         .route("GET /", index)
+//!         .layer(Logger::new())
+//!         .layer(CatchPanic::new())
+//!         .layer(RequestId::new())
+//!         .layer(SetHeaders::new().header("X-Frame-Options", "DENY"));
+//!     lambda_runtime::run(router).await?;
+//!     Ok(())
+//! }
+//!
+//! async fn index(_: http::Event) -> &'static str {
+//!     "Hello"
+//! }
+//! ```
+
+mod cors;
+
+pub use cors::Cors;
+
+use crate::http::{Event, Response, StatusCode, HeaderMap};
+use lambda_runtime::Error;
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Instant;
+
+/// Boxed future returned by middlewares and **Next**
+pub type BoxFuture = Pin<Box<dyn Future<Output = Result<Response, Error>> + Send>>;
+
+type Endpoint = Arc<dyn Fn(Event) -> BoxFuture + Send + Sync>;
+
+/// The rest of the middleware chain, ending with the handler
+#[derive(Clone)]
+pub struct Next {
+    middlewares: Arc<Vec<Arc<dyn Middleware>>>,
+    index: usize,
+    endpoint: Endpoint,
+}
+
+impl Next {
+    pub(crate) fn new(middlewares: Arc<Vec<Arc<dyn Middleware>>>, endpoint: Endpoint) -> Next {
+        Next {
+            middlewares,
+            index: 0,
+            endpoint,
+        }
+    }
+
+    /// Run the rest of the chain
+    pub fn run(mut self, event: Event) -> BoxFuture {
+        match self.middlewares.get(self.index).cloned() {
+            Some(middleware) => {
+                self.index += 1;
+                middleware.handle(event, self)
+            }
+            None => (self.endpoint)(event),
+        }
+    }
+}
+
+/// Code running before and after handlers
+///
+/// Implement the hooks for simple cases, or override `handle` to wrap the rest of the chain.
+///
+/// example:
+/// ```
+/// use hegel::http::{self, Response, StatusCode};
+/// use hegel::middleware::Middleware;
+///
+/// struct RequireApiKey;
+///
+/// impl Middleware for RequireApiKey {
+///     fn before(&self, event: &mut http::Event) -> Option<Response> {
+///         match event.payload.headers.get("x-api-key") {
+///             Some(_) => None,
+///             None => Some(Response::new_status(StatusCode::UNAUTHORIZED)),
+///         }
+///     }
+/// }
+/// ```
+pub trait Middleware: Send + Sync + 'static {
+    /// Run before the rest of the chain,
+    /// returning a Response short-circuits it (and this middleware's `after`)
+    fn before(&self, _event: &mut Event) -> Option<Response> {
+        None
+    }
+
+    /// Run on the Response of the rest of the chain
+    fn after(&self, _resp: &mut Response) {}
+
+    /// Map errors of the rest of the chain,
+    /// a Response returned here also goes through `after`
+    fn on_error(&self, err: Error) -> Result<Response, Error> {
+        Err(err)
+    }
+
+    /// Run this middleware around the rest of the chain
+    fn handle(self: Arc<Self>, mut event: Event, next: Next) -> BoxFuture {
+        if let Some(resp) = self.before(&mut event) {
+            return Box::pin(async move { Ok(resp) });
+        }
+        let fut = next.run(event);
+        Box::pin(async move {
+            let mut resp = fut.await.or_else(|e| self.on_error(e))?;
+            self.after(&mut resp);
+            Ok(resp)
+        })
+    }
+}
+
+/// Print a line for every request
+///
+/// example: `GET /items/42 200 3ms`
+#[derive(Clone)]
+pub struct Logger {
+    writer: Arc<dyn Fn(String) + Send + Sync>,
+}
+
+impl Logger {
+    /// new **Logger** printing to stdout (CloudWatch Logs on Lambda)
+    pub fn new() -> Logger {
+        Logger::with_writer(|line| println!("{}", line))
+    }
+
+    /// new **Logger** sending lines to provided function
+    pub fn with_writer<F: Fn(String) + Send + Sync + 'static>(writer: F) -> Logger {
+        Logger {
+            writer: Arc::new(writer),
+        }
+    }
+}
+
+impl Default for Logger {
+    fn default() -> Self {
+        Logger::new()
+    }
+}
+
+impl Middleware for Logger {
+    fn handle(self: Arc<Self>, event: Event, next: Next) -> BoxFuture {
+        let method = event.payload.method();
+        let path = event.payload.path();
+        let start = Instant::now();
+        let fut = next.run(event);
+        Box::pin(async move {
+            let result = fut.await;
+            let elapsed = start.elapsed().as_millis();
+            match &result {
+                Ok(resp) => (self.writer)(format!("{} {} {} {}ms", method, path, resp.status_code, elapsed)),
+                Err(e) => (self.writer)(format!("{} {} error {}ms: {}", method, path, elapsed, e)),
+            }
+            result
+        })
+    }
+}
+
+/// Answer `500 Internal Server Error` when the rest of the chain panics
+///
+/// Only works when the binary is built with `panic = "unwind"` (the default).
+///
+/// example:
+/// ```
+/// use lambda_runtime::{Context, LambdaEvent};
+/// use hegel::{http, router::Router};
+/// use hegel::middleware::{CatchPanic, RequestId};
+///
+/// async fn boom(_: http::Event) -> String {
+///     panic!("boom")
+/// }
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let router = Router::new()
+///     .route("$default", boom)
+///     .layer(RequestId::new())
+///     .layer(CatchPanic::new());
+///
+/// let mut req = http::Request::default();
+/// req.request_context.request_id = "abc".to_string();
+/// let resp = router.handle(LambdaEvent::new(req, Context::default())).await.unwrap();
+/// assert_eq!(resp.status_code, 500);
+/// assert_eq!(resp.headers.get("x-request-id"), Some("abc"));
+/// # });
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct CatchPanic;
+
+impl CatchPanic {
+    /// new **CatchPanic**
+    pub fn new() -> CatchPanic {
+        CatchPanic
+    }
+}
+
+impl Middleware for CatchPanic {
+    fn handle(self: Arc<Self>, event: Event, next: Next) -> BoxFuture {
+        match panic::catch_unwind(AssertUnwindSafe(|| next.run(event))) {
+            Ok(fut) => Box::pin(CatchUnwind(fut)),
+            Err(_) => Box::pin(async { Ok(Response::new_status(StatusCode::INTERNAL_SERVER_ERROR)) }),
+        }
+    }
+}
+
+struct CatchUnwind(BoxFuture);
+
+impl Future for CatchUnwind {
+    type Output = Result<Response, Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let fut = &mut self.0;
+        match panic::catch_unwind(AssertUnwindSafe(|| fut.as_mut().poll(cx))) {
+            Ok(poll) => poll,
+            Err(_) => Poll::Ready(Ok(Response::new_status(StatusCode::INTERNAL_SERVER_ERROR))),
+        }
+    }
+}
+
+/// Propagate a request ID to handlers and responses
+///
+/// The ID is read from the request header (`X-Request-Id` by default),
+/// or else taken from the API Gateway request ID.
+/// It is set on the request headers for handlers and on the response headers.
+#[derive(Debug, Clone)]
+pub struct RequestId {
+    header: String,
+}
+
+impl RequestId {
+    /// new **RequestId** using the `X-Request-Id` header
+    pub fn new() -> RequestId {
+        RequestId::with_header("X-Request-Id")
+    }
+
+    /// new **RequestId** using provided header
+    pub fn with_header<S: Into<String>>(header: S) -> RequestId {
+        RequestId {
+            header: header.into(),
+        }
+    }
+}
+
+impl Default for RequestId {
+    fn default() -> Self {
+        RequestId::new()
+    }
+}
+
+impl Middleware for RequestId {
+    fn handle(self: Arc<Self>, mut event: Event, next: Next) -> BoxFuture {
+        let id = match event.payload.headers.get(&self.header) {
+            Some(id) if !id.is_empty() => id.to_string(),
+            _ if !event.payload.request_context.request_id.is_empty() => event.payload.request_context.request_id.clone(),
+            _ => event.context.request_id.clone(),
+        };
+        event.payload.headers.insert(self.header.as_str(), id.as_str());
+        let fut = next.run(event);
+        Box::pin(async move {
+            let mut resp = fut.await?;
+            resp.headers.insert(self.header.as_str(), id);
+            Ok(resp)
+        })
+    }
+}
+
+/// Add headers to every response
+///
+/// example:
+/// ```
+/// use hegel::middleware::SetHeaders;
+///
+/// let security = SetHeaders::new()
+///     .header("Strict-Transport-Security", "max-age=31536000")
+///     .default_header("Cache-Control", "no-store");
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct SetHeaders {
+    headers: HeaderMap,
+    defaults: HeaderMap,
+}
+
+impl SetHeaders {
+    /// new empty **SetHeaders**
+    pub fn new() -> SetHeaders {
+        SetHeaders::default()
+    }
+
+    /// return a SetHeaders with provided header added,
+    /// replacing the header set by the handler
+    /// like s struct builder
+    pub fn header<K: Into<String>, V: Into<String>>(mut self, k: K, v: V) -> SetHeaders {
+        self.headers.append(k, v);
+        self
+    }
+
+    /// return a SetHeaders with provided header added,
+    /// only when the handler did not set it
+    /// like s struct builder
+    pub fn default_header<K: Into<String>, V: Into<String>>(mut self, k: K, v: V) -> SetHeaders {
+        self.defaults.append(k, v);
+        self
+    }
+}
+
+impl Middleware for SetHeaders {
+    fn after(&self, resp: &mut Response) {
+        for name in self.headers.names() {
+            resp.headers.remove(name);
+        }
+        for (k, v) in self.headers.iter() {
+            resp.headers.append(k, v);
+        }
+        for name in self.defaults.names() {
+            if !resp.headers.contains(name) {
+                for v in self.defaults.get_all(name) {
+                    resp.headers.append(name, v);
+                }
+            }
+        }
+    }
+}
+
+/// Compress response bodies the client accepts compressed
+///
+/// ! Remember to enable feature **compression** before using it !
+///
+/// See **http::Response::compress**
+///
+/// example:
+/// ```
+/// use lambda_runtime::{Context, LambdaEvent};
+/// use hegel::{http, router::Router};
+/// use hegel::middleware::Compression;
+///
+/// async fn items(_: http::Event) -> http::Response {
+///     http::Response::new_json(format!("[{}]", vec!["{\"id\":42}"; 1000].join(",")))
+/// }
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let router = Router::new().route("GET /items", items).layer(Compression::new());
+///
+/// let mut req = http::Request::default();
+/// req.request_context.http.method = "GET".to_string();
+/// req.request_context.http.path = "/items".to_string();
+/// req.headers.insert("Accept-Encoding", "gzip, br");
+/// let resp = router.handle(LambdaEvent::new(req, Context::default())).await.unwrap();
+/// assert_eq!(resp.headers.content_encoding(), Some("br"));
+/// # });
+/// ```
+#[cfg(feature = "compression")]
+#[derive(Debug, Default, Clone)]
+pub struct Compression;
+
+#[cfg(feature = "compression")]
+impl Compression {
+    /// new **Compression**
+    pub fn new() -> Compression {
+        Compression
+    }
+}
+
+#[cfg(feature = "compression")]
+impl Middleware for Compression {
+    fn handle(self: Arc<Self>, event: Event, next: Next) -> BoxFuture {
+        let mut req = HeaderMap::new();
+        for v in event.payload.headers.get_all("accept-encoding") {
+            req.append("Accept-Encoding", v);
+        }
+        let fut = next.run(event);
+        Box::pin(async move { Ok(fut.await?.compress(&req)) })
+    }
+}
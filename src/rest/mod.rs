@@ -0,0 +1,32 @@
+//! Payloads of API Gateway Lambda proxy integrations for REST APIs
+//!
+//! REST APIs send the same proxy event as HTTP APIs configured with payload format 1.0
+//! (without the `version` field), so the types are shared with **hegel::http::v1**.
+
+pub use crate::http::v1::{Request, Response};
+pub use crate::common::{RequestContextV1 as RequestContext, Identity};
+
+use lambda_runtime::LambdaEvent;
+
+/// Event for lambda_runtime service function
+/// Used for building API Gateway Lambda proxy integrations for REST APIs
+///
+/// example:
+/// ```rust,no_run
+/// use lambda_runtime::{service_fn, Error};
+/// use hegel::{http, rest};
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Error> {
+///     let func = service_fn(func);
+///     lambda_runtime::run(func).await?;
+///     Ok(())
+/// }
+///
+/// async fn func(req: rest::Event) -> Result<rest::Response, Error> {
+///     let caller = req.payload.user_arn().unwrap_or_default();
+///     Ok(http::Response::new_text(caller).into())
+/// }
+/// ```
+///
+pub type Event = LambdaEvent<Request>;
@@ -0,0 +1,84 @@
+use crate::http::{Event, IntoResponse, Response, extract::FromRequest};
+use crate::middleware::BoxFuture;
+use lambda_runtime::Error;
+use std::future::Future;
+
+/// Async functions which can be registered on a **Router**
+///
+/// It is implemented for
+/// - `async fn(http::Event) -> R`
+/// - `async fn(T1, T2, ...) -> R` with up to 8 arguments implementing **http::extract::FromRequest**
+///
+/// where `R` is **http::IntoResponse** or `Result<impl IntoResponse, lambda_runtime::Error>`.
+/// `M` only tells the implementations apart.
+pub trait Handler<M>: Send + Sync + 'static {
+    /// Handle an event
+    fn call(&self, event: Event) -> BoxFuture;
+}
+
+/// Handler return types, see **Handler**
+pub trait IntoHandlerResult {
+    /// Turn it into a handler result
+    fn into_handler_result(self) -> Result<Response, Error>;
+}
+
+impl<R: IntoResponse> IntoHandlerResult for R {
+    fn into_handler_result(self) -> Result<Response, Error> {
+        Ok(self.into_response())
+    }
+}
+
+impl<R: IntoResponse> IntoHandlerResult for Result<R, Error> {
+    fn into_handler_result(self) -> Result<Response, Error> {
+        self.map(IntoResponse::into_response)
+    }
+}
+
+impl<F, Fut> Handler<Event> for F
+where
+    F: Fn(Event) -> Fut + Send + Sync + 'static,
+    Fut: Future + Send + 'static,
+    Fut::Output: IntoHandlerResult,
+{
+    fn call(&self, event: Event) -> BoxFuture {
+        let fut = self(event);
+        Box::pin(async move { fut.await.into_handler_result() })
+    }
+}
+
+macro_rules! impl_handler {
+    ($($t:ident),*) => {
+        #[allow(non_snake_case, unused_variables)]
+        impl<F, Fut, $($t,)*> Handler<($($t,)*)> for F
+        where
+            F: Fn($($t),*) -> Fut + Send + Sync + 'static,
+            Fut: Future + Send + 'static,
+            Fut::Output: IntoHandlerResult,
+            $($t: FromRequest,)*
+        {
+            fn call(&self, event: Event) -> BoxFuture {
+                $(
+                    let $t = match $t::from_request(&event.payload, &event.context) {
+                        Ok(v) => v,
+                        Err(rejection) => {
+                            let resp = rejection.into_response();
+                            return Box::pin(async move { Ok(resp) });
+                        }
+                    };
+                )*
+                let fut = self($($t),*);
+                Box::pin(async move { fut.await.into_handler_result() })
+            }
+        }
+    };
+}
+
+impl_handler!();
+impl_handler!(T1);
+impl_handler!(T1, T2);
+impl_handler!(T1, T2, T3);
+impl_handler!(T1, T2, T3, T4);
+impl_handler!(T1, T2, T3, T4, T5);
+impl_handler!(T1, T2, T3, T4, T5, T6);
+impl_handler!(T1, T2, T3, T4, T5, T6, T7);
+impl_handler!(T1, T2, T3, T4, T5, T6, T7, T8);
@@ -0,0 +1,423 @@
+//! Dispatch HTTP API requests to handlers by API Gateway route keys
+//!
+//! Route keys look like the ones configured on the API Gateway side:
+//! `GET /items/{id}`, `ANY /files/{proxy+}` or `$default`.
+//!
+//! example:
+//! ```rust,no_run
+//! use lambda_runtime::Error;
+//! use hegel::{http, router::Router};
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Error> {
+//!     let router = Router::new()
+//!         .route("GET /items/{id}", get_item)
+//!         .route("ANY /files/{proxy+}", get_file)
+//!         .route("$default", not_found);
+//!     lambda_runtime::run(router).await?;
+//!     Ok(())
+//! }
+//!
+//! async fn get_item(req: http::Event) -> Result<http::Response, Error> {
+//!     let id = req.payload.params().unwrap_or_default().remove("id").unwrap_or_default();
+//!     Ok(http::Response::new_text(id))
+//! }
+//!
+//! async fn get_file(req: http::Event) -> Result<http::Response, Error> {
+//!     let proxy = req.payload.params().unwrap_or_default().remove("proxy").unwrap_or_default();
+//!     Ok(http::Response::new_text(proxy))
+//! }
+//!
+//! async fn not_found(_: http::Event) -> Result<http::Response, Error> {
+//!     Ok(http::Response::new_status(http::StatusCode::NOT_FOUND))
+//! }
+//! ```
+
+mod handler;
+
+pub use handler::{Handler, IntoHandlerResult};
+
+use crate::common;
+use crate::http::{Event, Response, StatusCode};
+use crate::middleware::{BoxFuture, Middleware, Next};
+use lambda_runtime::{Error, Service};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+type BoxHandler = Arc<dyn Fn(Event) -> BoxFuture + Send + Sync>;
+
+/// Error returned when a route key can not be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseRouteKeyError(String);
+
+impl fmt::Display for ParseRouteKeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid route key: {}", self.0)
+    }
+}
+
+impl std::error::Error for ParseRouteKeyError {}
+
+/// Path segment of a route key
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    /// `items`
+    Literal(String),
+    /// `{id}`
+    Param(String),
+    /// `{proxy+}`, only allowed as the last segment
+    Greedy(String),
+}
+
+/// A parsed API Gateway route key
+///
+/// example:
+/// ```
+/// use hegel::router::RouteKey;
+///
+/// let key: RouteKey = "GET /items/{id}".parse().unwrap();
+/// assert_eq!(key.method(), Some("GET"));
+/// assert_eq!(key.to_string(), "GET /items/{id}");
+///
+/// let params = key.matches("GET", "/items/42").unwrap();
+/// assert_eq!(params["id"], "42");
+/// assert!(key.matches("GET", "/items/42/parts").is_none());
+///
+/// let key: RouteKey = "ANY /files/{proxy+}".parse().unwrap();
+/// assert_eq!(key.matches("PUT", "/files/a/b.txt").unwrap()["proxy"], "a/b.txt");
+///
+/// let key: RouteKey = "GET /users/{user}/posts/{post}".parse().unwrap();
+/// assert_eq!(key.param_names(), vec!["user", "post"]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteKey {
+    /// `None` for `$default`
+    method: Option<String>,
+    segments: Vec<Segment>,
+}
+
+impl RouteKey {
+    /// Get the `$default` route key
+    pub fn default_route() -> RouteKey {
+        RouteKey {
+            method: None,
+            segments: Vec::new(),
+        }
+    }
+
+    /// Whether this is the `$default` route key
+    pub fn is_default(&self) -> bool {
+        self.method.is_none()
+    }
+
+    /// Get route method, `ANY` matches every method
+    ///
+    /// `None` for `$default`
+    pub fn method(&self) -> Option<&str> {
+        self.method.as_deref()
+    }
+
+    /// Get the names of the path params, in route order
+    pub fn param_names(&self) -> Vec<&str> {
+        self.segments.iter()
+            .filter_map(|s| match s {
+                Segment::Param(name) | Segment::Greedy(name) => Some(name.as_str()),
+                Segment::Literal(_) => None,
+            })
+            .collect()
+    }
+
+    /// Match a request method and path,
+    /// returning the path params when they match
+    ///
+    /// `$default` never matches here, it is only used when nothing else does.
+    pub fn matches(&self, method: &str, path: &str) -> Option<HashMap<String, String>> {
+        if !self.matches_path_only(path) {
+            return None;
+        }
+        match &self.method {
+            Some(m) if m == "ANY" || m.eq_ignore_ascii_case(method) => self.params(path),
+            _ => None,
+        }
+    }
+
+    fn matches_path_only(&self, path: &str) -> bool {
+        !self.is_default() && self.params(path).is_some()
+    }
+
+    fn params(&self, path: &str) -> Option<HashMap<String, String>> {
+        let mut params = HashMap::new();
+        let mut rest = path.strip_prefix('/')?;
+        for (i, seg) in self.segments.iter().enumerate() {
+            if let Segment::Greedy(name) = seg {
+                if rest.is_empty() {
+                    return None;
+                }
+                params.insert(name.clone(), common::percent_decode(rest));
+                return Some(params);
+            }
+            let (part, tail) = match rest.split_once('/') {
+                Some((part, tail)) => (part, Some(tail)),
+                None => (rest, None),
+            };
+            let part = common::percent_decode(part);
+            match seg {
+                Segment::Literal(l) if *l == part => {}
+                Segment::Param(name) if !part.is_empty() => {
+                    params.insert(name.clone(), part);
+                }
+                _ => return None,
+            }
+            match tail {
+                Some(tail) if i + 1 < self.segments.len() => rest = tail,
+                None if i + 1 == self.segments.len() => return Some(params),
+                _ => return None,
+            }
+        }
+        None
+    }
+
+    /// Rank used to pick the most specific route when several match,
+    /// literal segments beat `{param}` which beats `{proxy+}`,
+    /// and a named method beats `ANY`
+    fn rank(&self) -> (Vec<u8>, bool) {
+        let segments = self.segments.iter().map(|s| match s {
+            Segment::Literal(_) => 2,
+            Segment::Param(_) => 1,
+            Segment::Greedy(_) => 0,
+        }).collect();
+        (segments, self.method.as_deref() != Some("ANY"))
+    }
+}
+
+impl std::str::FromStr for RouteKey {
+    type Err = ParseRouteKeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ParseRouteKeyError(s.to_string());
+        if s == "$default" {
+            return Ok(RouteKey::default_route());
+        }
+        let (method, path) = s.split_once(' ').ok_or_else(err)?;
+        if method.is_empty() || !method.bytes().all(|b| b.is_ascii_alphabetic()) {
+            return Err(err());
+        }
+        let path = path.strip_prefix('/').ok_or_else(err)?;
+        let parts: Vec<&str> = path.split('/').collect();
+        let mut segments = Vec::with_capacity(parts.len());
+        for (i, part) in parts.iter().enumerate() {
+            let segment = match part.strip_prefix('{').and_then(|p| p.strip_suffix('}')) {
+                Some(name) => match name.strip_suffix('+') {
+                    Some(name) if i + 1 == parts.len() => Segment::Greedy(name.to_string()),
+                    Some(_) => return Err(err()),
+                    None => Segment::Param(name.to_string()),
+                },
+                None => Segment::Literal(part.to_string()),
+            };
+            match &segment {
+                Segment::Param(name) | Segment::Greedy(name) if name.is_empty() || name.contains(['{', '}']) => return Err(err()),
+                Segment::Literal(l) if l.contains(['{', '}']) => return Err(err()),
+                _ => {}
+            }
+            segments.push(segment);
+        }
+        Ok(RouteKey {
+            method: Some(method.to_ascii_uppercase()),
+            segments,
+        })
+    }
+}
+
+impl fmt::Display for RouteKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let method = match &self.method {
+            Some(m) => m,
+            None => return f.write_str("$default"),
+        };
+        write!(f, "{} ", method)?;
+        for seg in &self.segments {
+            match seg {
+                Segment::Literal(l) => write!(f, "/{}", l)?,
+                Segment::Param(p) => write!(f, "/{{{}}}", p)?,
+                Segment::Greedy(p) => write!(f, "/{{{}+}}", p)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+struct Route {
+    key: RouteKey,
+    handler: BoxHandler,
+}
+
+/// Router dispatching **http::Event** to handlers by route key
+///
+/// A request is matched on method plus path first, the most specific route wins.
+/// When nothing matches, the payload's `route_key` is looked up
+/// (useful when the path carries a stage prefix), then the `$default` route.
+/// Without a `$default` route, a path known with other methods gets
+/// `405 Method Not Allowed` with an `Allow` header, otherwise `404 Not Found`.
+///
+/// The matched handler receives the path params in `path_parameters`
+/// and the matched route key in `route_key`.
+/// Handlers take the whole **http::Event** or extractors from **http::extract**, see **Handler**.
+///
+/// example:
+/// ```
+/// use lambda_runtime::{Context, Error, LambdaEvent};
+/// use hegel::{http, router::Router};
+///
+/// async fn get_item(req: http::Event) -> Result<http::Response, Error> {
+///     let params = req.payload.params().unwrap_or_default();
+///     Ok(http::Response::new_text(format!("{} {}", req.payload.route_key, params["id"])))
+/// }
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let router = Router::new().route("GET /items/{id}", get_item);
+///
+/// let mut req = http::Request::default();
+/// req.request_context.http.method = "GET".to_string();
+/// req.request_context.http.path = "/items/42".to_string();
+/// let resp = router.handle(LambdaEvent::new(req.clone(), Context::default())).await.unwrap();
+/// assert_eq!(resp.body, "GET /items/{id} 42");
+///
+/// req.request_context.http.method = "DELETE".to_string();
+/// let resp = router.handle(LambdaEvent::new(req.clone(), Context::default())).await.unwrap();
+/// assert_eq!(resp.status_code, 405);
+/// assert_eq!(resp.headers.get("allow"), Some("GET"));
+///
+/// req.request_context.http.path = "/users".to_string();
+/// let resp = router.handle(LambdaEvent::new(req, Context::default())).await.unwrap();
+/// assert_eq!(resp.status_code, 404);
+/// # });
+/// ```
+#[derive(Clone, Default)]
+pub struct Router {
+    routes: Arc<Vec<Route>>,
+    default: Option<BoxHandler>,
+    middlewares: Arc<Vec<Arc<dyn Middleware>>>,
+}
+
+impl Router {
+    /// new empty **Router**
+    pub fn new() -> Router {
+        Router::default()
+    }
+
+    /// return a Router with provided handler registered for the route key
+    /// like s struct builder
+    ///
+    /// Registering the same route key again replaces its handler.
+    ///
+    /// # Panics
+    /// Panics if the route key is malformed, use `try_route` to handle it.
+    pub fn route<H: Handler<M>, M>(self, key: &str, handler: H) -> Router {
+        match self.try_route(key, handler) {
+            Ok(router) => router,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// return a Router with provided handler registered for the route key,
+    /// or an error when the route key is malformed
+    pub fn try_route<H: Handler<M>, M>(mut self, key: &str, handler: H) -> Result<Router, ParseRouteKeyError> {
+        let key: RouteKey = key.parse()?;
+        let handler: BoxHandler = Arc::new(move |event| handler.call(event));
+        if key.is_default() {
+            self.default = Some(handler);
+            return Ok(self);
+        }
+        let routes = Arc::make_mut(&mut self.routes);
+        routes.retain(|r| r.key != key);
+        routes.push(Route { key, handler });
+        Ok(self)
+    }
+
+    /// return a Router with provided middleware added around all routes,
+    /// including the `404` / `405` answers
+    /// like s struct builder
+    ///
+    /// The first middleware added is the outermost, see **hegel::middleware**.
+    pub fn layer<T: Middleware>(mut self, middleware: T) -> Router {
+        Arc::make_mut(&mut self.middlewares).push(Arc::new(middleware));
+        self
+    }
+
+    /// Dispatch an event through the middlewares to the matching handler
+    pub async fn handle(&self, event: Event) -> Result<Response, Error> {
+        if self.middlewares.is_empty() {
+            return self.dispatch(event).await;
+        }
+        let router = self.clone();
+        let endpoint = Arc::new(move |event| {
+            let router = router.clone();
+            Box::pin(async move { router.dispatch(event).await }) as BoxFuture
+        });
+        Next::new(self.middlewares.clone(), endpoint).run(event).await
+    }
+
+    async fn dispatch(&self, mut event: Event) -> Result<Response, Error> {
+        let method = event.payload.method();
+        let path = event.payload.path();
+
+        let matched = self.routes.iter()
+            .filter_map(|r| r.key.matches(&method, &path).map(|params| (r, params)))
+            .max_by(|(a, _), (b, _)| a.key.rank().cmp(&b.key.rank()));
+        if let Some((route, params)) = matched {
+            let payload = &mut event.payload;
+            payload.path_parameters.get_or_insert_with(HashMap::new).extend(params);
+            payload.route_key = route.key.to_string();
+            return (route.handler)(event).await;
+        }
+
+        let by_route_key = event.payload.route_key.parse::<RouteKey>().ok()
+            .filter(|k| !k.is_default())
+            .and_then(|k| self.routes.iter().find(|r| r.key == k));
+        if let Some(route) = by_route_key {
+            return (route.handler)(event).await;
+        }
+
+        if let Some(handler) = &self.default {
+            return handler(event).await;
+        }
+
+        let allow: BTreeSet<&str> = self.routes.iter()
+            .filter(|r| r.key.matches_path_only(&path))
+            .filter_map(|r| r.key.method())
+            .collect();
+        if allow.is_empty() {
+            return Ok(Response::new_status(StatusCode::NOT_FOUND));
+        }
+        let allow = allow.into_iter().collect::<Vec<_>>().join(", ");
+        Ok(Response::new_status(StatusCode::METHOD_NOT_ALLOWED).header("Allow".to_string(), allow))
+    }
+}
+
+impl fmt::Debug for Router {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Router")
+            .field("routes", &self.routes.iter().map(|r| r.key.to_string()).collect::<Vec<_>>())
+            .field("default", &self.default.is_some())
+            .field("middlewares", &self.middlewares.len())
+            .finish()
+    }
+}
+
+impl Service<Event> for Router {
+    type Response = Response;
+    type Error = Error;
+    type Future = BoxFuture;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, event: Event) -> Self::Future {
+        let router = self.clone();
+        Box::pin(async move { router.handle(event).await })
+    }
+}
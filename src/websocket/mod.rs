@@ -0,0 +1,37 @@
+pub mod req;
+pub mod resp;
+
+pub use resp::Response;
+pub use req::{Request, RequestContext, EventType};
+
+use lambda_runtime::LambdaEvent;
+
+/// Event for lambda_runtime service function
+/// Used for building API Gateway Lambda integrations for WebSocket APIs
+///
+/// example:
+/// ```rust,no_run
+/// use lambda_runtime::{service_fn, Error};
+/// use hegel::websocket::{self, EventType};
+/// use hegel::http::StatusCode;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Error> {
+///     let func = service_fn(func);
+///     lambda_runtime::run(func).await?;
+///     Ok(())
+/// }
+///
+/// async fn func(req: websocket::Event) -> Result<websocket::Response, Error> {
+///     match req.payload.event_type() {
+///         EventType::Connect => Ok(websocket::Response::new_status(StatusCode::OK)),
+///         EventType::Disconnect => Ok(websocket::Response::new_status(StatusCode::OK)),
+///         EventType::Message => {
+///             let body = req.payload.body()?.unwrap_or_default();
+///             Ok(websocket::Response::new_text(body))
+///         }
+///     }
+/// }
+/// ```
+///
+pub type Event = LambdaEvent<Request>;
@@ -0,0 +1,184 @@
+use crate::common;
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use serde::{Serialize, Deserialize};
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, TimeZone, Utc};
+
+pub use common::ParseBodyError;
+
+/// **lambda_runtime** service payload type
+/// Used for building API Gateway Lambda integrations for WebSocket APIs
+///
+/// Headers and queries are only sent with `$connect` events
+///
+/// example:
+/// ```
+/// use hegel::{websocket, http::StatusCode};
+/// use lambda_runtime::{Error, LambdaEvent};
+///
+/// async fn handler(req: LambdaEvent<websocket::Request>) -> Result<websocket::Response, Error> {
+///     //...
+///     Ok(websocket::Response::new_status(StatusCode::OK))
+/// }
+/// ```
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    pub headers: Option<HashMap<String, String>>,
+    pub multi_value_headers: Option<HashMap<String, Vec<String>>>,
+    pub query_string_parameters: Option<HashMap<String, String>>,
+    pub multi_value_query_string_parameters: Option<HashMap<String, Vec<String>>>,
+    pub request_context: RequestContext,
+    pub stage_variables: Option<HashMap<String, String>>,
+    pub body: Option<String>,
+    #[serde(default, rename = "isBase64Encoded")]
+    pub is_base64encoded: bool,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestContext {
+    pub route_key: String,
+    pub event_type: EventType,
+    pub message_id: Option<String>,
+    pub extended_request_id: Option<String>,
+    pub request_time: Option<String>,
+    pub message_direction: Option<String>,
+    pub disconnect_status_code: Option<u16>,
+    pub disconnect_reason: Option<String>,
+    pub stage: String,
+    pub connected_at: u64,
+    pub request_time_epoch: u64,
+    pub identity: common::Identity,
+    pub authorizer: Option<HashMap<String, serde_json::Value>>,
+    pub request_id: String,
+    pub domain_name: String,
+    pub connection_id: String,
+    pub api_id: String,
+}
+
+/// Type of a WebSocket event
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum EventType {
+    /// `$connect` route
+    Connect,
+    /// `$disconnect` route
+    Disconnect,
+    /// `$default` and custom routes
+    #[default]
+    Message,
+}
+
+impl Request {
+    /// Get the message body as String (UTF-8)
+    pub fn body(&self) -> Result<Option<String>, ParseBodyError> {
+        common::decode_body(&self.body, self.is_base64encoded)
+    }
+
+    /// Get the message body as binary
+    pub fn body_binary(&self) -> Result<Option<Vec<u8>>, base64::DecodeError> {
+        common::decode_body_binary(&self.body, self.is_base64encoded)
+    }
+
+    /// Get the type of the event
+    pub fn event_type(&self) -> EventType {
+        self.request_context.event_type
+    }
+
+    /// Check whether the event is a `$connect` event
+    pub fn is_connect(&self) -> bool {
+        self.request_context.event_type == EventType::Connect
+    }
+
+    /// Check whether the event is a `$disconnect` event
+    pub fn is_disconnect(&self) -> bool {
+        self.request_context.event_type == EventType::Disconnect
+    }
+
+    /// Check whether the event carries a message
+    pub fn is_message(&self) -> bool {
+        self.request_context.event_type == EventType::Message
+    }
+
+    /// Get the route the event was sent to
+    ///
+    /// example: `$connect`, `$default`, `sendmessage`
+    pub fn route_key(&self) -> String {
+        self.request_context.route_key.clone()
+    }
+
+    /// Get the ID of the client connection
+    pub fn connection_id(&self) -> String {
+        self.request_context.connection_id.clone()
+    }
+
+    /// Get the ID of the message (message events only)
+    pub fn message_id(&self) -> Option<String> {
+        self.request_context.message_id.clone()
+    }
+
+    /// Get the endpoint of the API Gateway Management API for this stage,
+    /// used to send messages to or close client connections
+    ///
+    /// example: `https://abcdef123.execute-api.us-east-1.amazonaws.com/prod`
+    pub fn management_endpoint(&self) -> String {
+        format!("https://{}/{}", self.request_context.domain_name, self.request_context.stage)
+    }
+
+    /// Get the URL of this connection in the API Gateway Management API
+    ///
+    /// `POST` sends a message to the client, `GET` gets the connection status
+    /// and `DELETE` closes the connection
+    ///
+    /// example: `https://abcdef123.execute-api.us-east-1.amazonaws.com/prod/@connections/L0SM9cOFvHcCIhw=`
+    pub fn connection_url(&self) -> String {
+        format!("{}/@connections/{}", self.management_endpoint(), self.request_context.connection_id)
+    }
+
+    /// Get user request headers (`$connect` events only)
+    pub fn headers(&self) -> HashMap<String, String> {
+        self.headers.clone().unwrap_or_default()
+    }
+
+    /// Get user request queries (`$connect` events only)
+    pub fn queries(&self) -> Option<HashMap<String, String>> {
+        self.query_string_parameters.clone()
+    }
+
+    /// Get API Gateway stage
+    ///
+    /// example: `prod`
+    pub fn stage(&self) -> String {
+        self.request_context.stage.clone()
+    }
+
+    /// Get request datetime (Instant)
+    pub fn time(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_millis(self.request_context.request_time_epoch)
+    }
+
+    /// Get connection datetime (Instant)
+    pub fn connected_at(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_millis(self.request_context.connected_at)
+    }
+
+    /// Get request datetime with **chrono::DateTime** type output
+    /// ! Remember to enable feature **chrono** before using it !
+    #[cfg(feature = "chrono")]
+    pub fn time_chrono(&self) -> DateTime<Utc> {
+        Utc.timestamp_millis(self.request_context.request_time_epoch as i64)
+    }
+
+    /// Get user request IP
+    pub fn ip(&self) -> String {
+        self.request_context.identity.source_ip.clone()
+    }
+
+    /// Get user request User-Agent
+    pub fn ua(&self) -> String {
+        self.request_context.identity.user_agent.clone().unwrap_or_default()
+    }
+}
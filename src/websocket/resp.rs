@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+use serde::{Serialize, Deserialize};
+use crate::http::{StatusCode, InvalidStatusCode};
+
+/// **lambda_runtime** service function return payload type
+/// Used for building API Gateway Lambda integrations for WebSocket APIs
+///
+/// A non-2xx status code rejects `$connect` events,
+/// the body is sent back to the client on routes with two-way communication
+///
+/// example:
+/// ```
+/// use hegel::{websocket, http::StatusCode};
+/// use lambda_runtime::Error;
+///
+/// async fn handler(req: websocket::Event) -> Result<websocket::Response, Error> {
+///     if req.payload.is_connect() && req.payload.queries().is_none() {
+///         return Ok(websocket::Response::new_status(StatusCode::UNAUTHORIZED));
+///     }
+///     Ok(websocket::Response::new_status(StatusCode::OK))
+/// }
+/// ```
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Response {
+    pub status_code: StatusCode,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub headers: HashMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    #[serde(rename = "isBase64Encoded")]
+    pub is_base64encoded: bool,
+}
+
+impl Response {
+    /// return a HTTP status without body as Response
+    pub fn new_status(s: StatusCode) -> Response {
+        Response {
+            status_code: s,
+            ..Default::default()
+        }
+    }
+
+    /// return a HTTP status number without body as Response,
+    /// or an error when it is not a valid status code (`100..=599`)
+    pub fn try_new_status(s: u16) -> Result<Response, InvalidStatusCode> {
+        StatusCode::try_from(s).map(Response::new_status)
+    }
+
+    /// return text(UTF-8) as Response
+    pub fn new_text(b: String) -> Response {
+        Response::new_status(StatusCode::OK).body_text(b)
+    }
+
+    /// return binary data as Response
+    pub fn new_binary(b: Vec<u8>) -> Response {
+        Response::new_status(StatusCode::OK).body_binary(b)
+    }
+
+    /// return a Response with provided header added (`$connect` events only)
+    /// like s struct builder
+    pub fn header(mut self, k: String, v: String) -> Response {
+        self.headers.insert(k, v);
+        self
+    }
+
+    /// return a Response with provided status code added
+    /// like s struct builder
+    pub fn status_code(mut self, s: StatusCode) -> Response {
+        self.status_code = s;
+        self
+    }
+
+    /// return a Response with provided text body added
+    /// like s struct builder
+    pub fn body_text(mut self, b: String) -> Response {
+        self.body = Some(b);
+        self.is_base64encoded = false;
+        self
+    }
+
+    /// return a Response with provided binary body added
+    /// like s struct builder
+    pub fn body_binary(mut self, b: Vec<u8>) -> Response {
+        self.body = Some(base64::encode(b));
+        self.is_base64encoded = true;
+        self
+    }
+}